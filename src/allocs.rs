@@ -0,0 +1,184 @@
+use std::cell::Cell;
+
+#[cfg(feature = "count-allocs")]
+use std::alloc::{GlobalAlloc, Layout, System};
+
+/*
+Heap allocation counting, for checking the solutions that are meant not to allocate.
+
+Only built with `--features count-allocs`, which swaps in a global allocator that counts
+every allocation made on the current thread before handing it on to the system allocator.
+Counts are per thread because each solve runs on its own worker thread.
+
+A realloc counts as one allocation of the new size, the same way dhat counts them.
+*/
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Allocs {
+    pub count: usize,
+    pub bytes: usize,
+    // most bytes allocated at once
+    pub peak: usize,
+    // bytes still allocated at the end
+    pub live: usize,
+}
+
+thread_local! {
+    static COUNTS: Cell<Allocs> = const {
+        Cell::new(Allocs { count: 0, bytes: 0, peak: 0, live: 0 })
+    };
+}
+
+#[cfg(feature = "count-allocs")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[cfg(feature = "count-allocs")]
+struct Counting;
+
+#[cfg(feature = "count-allocs")]
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            allocated(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        freed(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            allocated(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+// `try_with` as the allocator can still be called while a thread is being torn down
+#[cfg(feature = "count-allocs")]
+fn allocated(size: usize, replaced: usize) {
+    let _ = COUNTS.try_with(|counts| {
+        let mut allocs = counts.get();
+        allocs.count += 1;
+        allocs.bytes += size;
+        allocs.live = allocs.live.saturating_sub(replaced) + size;
+        allocs.peak = allocs.peak.max(allocs.live);
+        counts.set(allocs);
+    });
+}
+
+#[cfg(feature = "count-allocs")]
+fn freed(size: usize) {
+    let _ = COUNTS.try_with(|counts| {
+        let mut allocs = counts.get();
+        // memory from before counting started can be freed too
+        allocs.live = allocs.live.saturating_sub(size);
+        counts.set(allocs);
+    });
+}
+
+// counts the allocations `f` makes on this thread, or `None` without the count-allocs feature
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, Option<Allocs>) {
+    if !cfg!(feature = "count-allocs") {
+        return (f(), None);
+    }
+
+    let before = COUNTS.with(|counts| counts.replace(Allocs::default()));
+    let output = f();
+    let allocs = COUNTS.with(|counts| counts.replace(before));
+
+    (output, Some(allocs))
+}
+
+impl Allocs {
+    // `self` then `next`, with whatever `self` left allocated still around during `next`
+    pub fn then(&self, next: &Allocs) -> Allocs {
+        Allocs {
+            count: self.count + next.count,
+            bytes: self.bytes + next.bytes,
+            peak: self.peak.max(self.live + next.peak),
+            live: self.live + next.live,
+        }
+    }
+
+    // this part's share, when several parts share one parse,
+    // with the peak and what's left allocated split the same way so the shares still add up
+    pub fn amortized(&self, parts: usize) -> Allocs {
+        Allocs {
+            count: self.count / parts,
+            bytes: self.bytes / parts,
+            peak: self.peak / parts,
+            live: self.live / parts,
+        }
+    }
+}
+
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(1536), "1.5 KiB");
+    assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+}
+
+#[test]
+fn test_amortized_then() {
+    let parse = Allocs { count: 4, bytes: 1000, peak: 800, live: 600 };
+    let solve = Allocs { count: 2, bytes: 100, peak: 100, live: 0 };
+
+    let share = parse.amortized(2);
+    let part = share.then(&solve);
+    assert_eq!(part, Allocs { count: 4, bytes: 600, peak: 400, live: 300 });
+    assert!(part.peak <= part.bytes);
+
+    // both parts' shares add back up to the whole parse
+    assert_eq!(share.live * 2, parse.live);
+    assert_eq!(share.bytes * 2, parse.bytes);
+}
+
+#[cfg(feature = "count-allocs")]
+#[test]
+fn test_count() {
+    let (_, allocs) = count(|| {
+        let mut v: Vec<u64> = Vec::with_capacity(4);
+        v.extend([1, 2, 3, 4, 5]);
+        drop(v);
+        Box::new(1u32)
+    });
+    let allocs = allocs.unwrap();
+
+    assert_eq!(allocs.count, 3);
+    assert_eq!(allocs.bytes, 32 + 64 + 4);
+    assert_eq!(allocs.peak, 64);
+    assert_eq!(allocs.live, 4);
+}
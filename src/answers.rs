@@ -0,0 +1,137 @@
+use std::{fs::{create_dir_all, read_to_string, write}, io::ErrorKind, path::Path};
+
+/*
+Accepted answers, so refactors can be checked against them.
+
+Each day gets a file at answers/YEAR/day_N.txt with a line per known part, e.g.
+1: 55477
+2: 54431
+Parts without a line are unknown.
+`submit` adds a part's line once its answer is accepted.
+
+Alternate inputs at inputs/YEAR/day_N/NAME.txt have their answers at answers/YEAR/day_N/NAME.txt.
+*/
+
+pub const ANSWERS_DIR: &str = "answers";
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Answers {
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: String },
+    Unknown,
+}
+
+impl Verdict {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong { .. } => "wrong",
+            Verdict::Unknown => "unknown",
+        }
+    }
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    // a part that used to have an answer and now fails counts as wrong too
+    pub fn verify(&self, part: u8, answer: Option<&str>) -> Verdict {
+        match (self.get(part), answer) {
+            (None, _) => Verdict::Unknown,
+            (Some(expected), Some(answer)) if expected == answer => Verdict::Correct,
+            (Some(expected), _) => Verdict::Wrong {
+                expected: expected.to_string(),
+            },
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) -> Result<(), String> {
+        match part {
+            1 => self.part_one = Some(answer.to_string()),
+            2 => self.part_two = Some(answer.to_string()),
+            part => return Err(format!("There's no part {part} to save an answer for")),
+        }
+
+        Ok(())
+    }
+
+    fn to_file(&self) -> String {
+        [(1, &self.part_one), (2, &self.part_two)].iter()
+            .filter_map(|(part, answer)| Some(format!("{part}: {}\n", answer.as_ref()?)))
+            .collect()
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        let mut answers = Answers::default();
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let (part, answer) = line.split_once(':')
+                .ok_or_else(|| format!("Couldn't parse answer from line `{line}`"))?;
+
+            let answer = Some(answer.trim().to_string());
+
+            match part.trim() {
+                "1" => answers.part_one = answer,
+                "2" => answers.part_two = answer,
+                _ => return Err(format!("Unknown part in line `{line}`")),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+pub fn load(year: u16, day: u8) -> Result<Answers, String> {
+    load_path(&format!("{ANSWERS_DIR}/{year}/day_{day}.txt"))
+}
+
+pub fn load_alternate(year: u16, day: u8, name: &str) -> Result<Answers, String> {
+    load_path(&format!("{ANSWERS_DIR}/{year}/day_{day}/{name}.txt"))
+}
+
+pub fn save(year: u16, day: u8, answers: &Answers) -> Result<(), String> {
+    let path = format!("{ANSWERS_DIR}/{year}/day_{day}.txt");
+
+    if let Some(dir) = Path::new(&path).parent() {
+        create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    }
+
+    write(&path, answers.to_file()).map_err(|e| format!("{path}: {e}"))
+}
+
+fn load_path(path: &str) -> Result<Answers, String> {
+    match read_to_string(path) {
+        Ok(contents) => Answers::parse(&contents).map_err(|e| format!("{path}: {e}")),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(format!("{path}: {e}")),
+    }
+}
+
+#[test]
+fn test_parse() {
+    let answers = Answers::parse("1: 55477\n\n2: 54431\n").unwrap();
+
+    assert_eq!(answers.verify(1, Some("55477")), Verdict::Correct);
+    assert_eq!(answers.verify(2, Some("1")), Verdict::Wrong { expected: "54431".to_string() });
+    assert_eq!(answers.verify(2, None), Verdict::Wrong { expected: "54431".to_string() });
+    assert_eq!(Answers::parse("2: 7").unwrap().verify(1, Some("7")), Verdict::Unknown);
+    assert!(Answers::parse("3: 7").is_err());
+
+    let mut answers = Answers::parse("2: 54431\n").unwrap();
+    assert_eq!(answers.to_file(), "2: 54431\n");
+    answers.set(1, "55477").unwrap();
+    assert_eq!(answers.to_file(), "1: 55477\n2: 54431\n");
+    assert!(answers.set(3, "1").is_err());
+}
@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, fs::{read_to_string, write}, path::Path, time::Duration};
+
+/*
+Saved runtimes to compare a run against, e.g. before and after a refactor.
+
+`--save-baseline PATH` writes one line per part that solved, tab separated:
+year, day, part, duration in nanoseconds, standard deviation in nanoseconds (0 outside bench mode)
+
+`--compare PATH` marks each part as faster, slower or unchanged against that file.
+A change only counts once it's bigger than the noise, which is twice the combined standard deviation
+of both runs when there are bench statistics, and a flat 5% of the baseline otherwise.
+*/
+
+const NOISE_WITHOUT_STATS: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Entry {
+    pub duration: Duration,
+    pub std_dev: Option<Duration>,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<(u16, u8, u8), Entry>,
+}
+
+// percentages are relative to the baseline, positive being slower
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Change {
+    Faster(f64),
+    Slower(f64),
+    Unchanged(f64),
+    // nothing in the baseline to compare to
+    New,
+}
+
+impl Change {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Change::Faster(_) => "faster",
+            Change::Slower(_) => "slower",
+            Change::Unchanged(_) => "unchanged",
+            Change::New => "new",
+        }
+    }
+
+    pub fn percent(&self) -> Option<f64> {
+        match self {
+            Change::Faster(percent) | Change::Slower(percent) | Change::Unchanged(percent) => Some(*percent),
+            Change::New => None,
+        }
+    }
+}
+
+impl Baseline {
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, entry: Entry) {
+        self.entries.insert((year, day, part), entry);
+    }
+
+    pub fn compare(&self, year: u16, day: u8, part: u8, current: Entry) -> Change {
+        let Some(baseline) = self.entries.get(&(year, day, part)) else {
+            return Change::New;
+        };
+
+        let base = baseline.duration.as_nanos() as f64;
+        let now = current.duration.as_nanos() as f64;
+
+        let noise = match (baseline.std_dev, current.std_dev) {
+            (None, None) => base * NOISE_WITHOUT_STATS,
+            (base_sd, now_sd) => {
+                let variance = |sd: Option<Duration>| sd.map_or(0.0, |sd| (sd.as_nanos() as f64).powi(2));
+                2.0 * (variance(base_sd) + variance(now_sd)).sqrt()
+            },
+        };
+
+        let percent = if base > 0.0 { (now / base - 1.0) * 100.0 } else { 0.0 };
+
+        if (now - base).abs() <= noise {
+            Change::Unchanged(percent)
+        } else if now < base {
+            Change::Faster(percent)
+        } else {
+            Change::Slower(percent)
+        }
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        let mut baseline = Baseline::default();
+
+        for line in input.lines().filter(|line| !line.trim().is_empty()) {
+            let parse = |line: &str| -> Option<(u16, u8, u8, Entry)> {
+                let mut fields = line.split('\t');
+                let year = fields.next()?.parse().ok()?;
+                let day = fields.next()?.parse().ok()?;
+                let part = fields.next()?.parse().ok()?;
+                let duration = Duration::from_nanos(fields.next()?.parse().ok()?);
+                let std_dev = Some(Duration::from_nanos(fields.next()?.parse().ok()?))
+                    .filter(|std_dev| !std_dev.is_zero());
+
+                Some((year, day, part, Entry { duration, std_dev }))
+            };
+
+            let (year, day, part, entry) = parse(line)
+                .ok_or_else(|| format!("Couldn't parse baseline entry from line `{line}`"))?;
+            baseline.insert(year, day, part, entry);
+        }
+
+        Ok(baseline)
+    }
+
+    fn to_tsv(&self) -> String {
+        self.entries.iter()
+            .map(|((year, day, part), entry)| {
+                format!(
+                    "{year}\t{day}\t{part}\t{}\t{}\n",
+                    entry.duration.as_nanos(),
+                    entry.std_dev.unwrap_or_default().as_nanos(),
+                )
+            })
+            .collect()
+    }
+}
+
+pub fn load(path: &Path) -> Result<Baseline, String> {
+    let contents = read_to_string(path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    Baseline::parse(&contents).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+pub fn save(path: &Path, baseline: &Baseline) -> Result<(), String> {
+    write(path, baseline.to_tsv())
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+#[test]
+fn test_compare() {
+    let entry = |micros: u64, std_dev: Option<u64>| Entry {
+        duration: Duration::from_micros(micros),
+        std_dev: std_dev.map(Duration::from_micros),
+    };
+
+    let baseline = Baseline::parse("2023\t1\t1\t100000\t0\n2023\t1\t2\t100000\t5000\n").unwrap();
+    assert_eq!(Baseline::parse(&baseline.to_tsv()).unwrap(), baseline);
+
+    // without stats, anything within 5% is noise
+    assert_eq!(baseline.compare(2023, 1, 1, entry(104, None)).name(), "unchanged");
+    assert!(matches!(baseline.compare(2023, 1, 1, entry(110, None)), Change::Slower(percent) if (percent - 10.0).abs() < 1e-9));
+    assert_eq!(baseline.compare(2023, 1, 1, entry(80, None)).name(), "faster");
+
+    // 2 * sqrt(5^2 + 5^2) is about 14us of noise
+    assert_eq!(baseline.compare(2023, 1, 2, entry(113, Some(5))).name(), "unchanged");
+    assert_eq!(baseline.compare(2023, 1, 2, entry(115, Some(5))).name(), "slower");
+
+    assert_eq!(baseline.compare(2023, 2, 1, entry(1, None)), Change::New);
+    // nor for another year
+    assert_eq!(baseline.compare(2022, 1, 1, entry(100, None)), Change::New);
+}
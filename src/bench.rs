@@ -0,0 +1,178 @@
+use std::time::{Duration, Instant};
+
+use crate::solutions::{error::Error, solved::{cancelled, Solved}};
+
+/*
+Benchmark mode, for when a single cold run isn't good enough.
+
+Each solution is warmed up first (caches, branch predictors, page faults on the input),
+then run `samples` times. Outliers are classified with Tukey's fences, the same way criterion does:
+anything more than 1.5 IQR outside the quartiles is mild, more than 3 IQR is severe.
+
+The runner benchmarks on a worker, with `time_limit` allowing for every run,
+and stops between runs once the worker has been given up on.
+*/
+
+const WARM_UP_TIME: Duration = Duration::from_millis(200);
+const WARM_UP_MIN_RUNS: usize = 3;
+
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub std_dev: Duration,
+    pub outliers: Outliers,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Outliers {
+    pub low_severe: usize,
+    pub low_mild: usize,
+    pub high_mild: usize,
+    pub high_severe: usize,
+}
+
+// the time limit for a whole benchmark, given the limit for a single run
+pub fn time_limit(per_run: Duration, samples: usize) -> Duration {
+    WARM_UP_TIME + per_run.saturating_mul((WARM_UP_MIN_RUNS + samples.max(1)) as u32)
+}
+
+pub fn bench(solution: &dyn Solved, input: &str, samples: usize) -> Result<(String, Stats), Error> {
+    let stop = || Error::Internal("benchmark cancelled".to_string());
+
+    let warm_up_start = Instant::now();
+    let mut runs = 0;
+
+    while runs < WARM_UP_MIN_RUNS || warm_up_start.elapsed() < WARM_UP_TIME {
+        if cancelled() {
+            return Err(stop());
+        }
+
+        solution.solve(input)?;
+        runs += 1;
+    }
+
+    let mut output = String::new();
+    let mut durations = Vec::with_capacity(samples);
+
+    for _ in 0..samples.max(1) {
+        if cancelled() {
+            return Err(stop());
+        }
+
+        let (answer, duration) = solution.solve_timed(input)?;
+        output = answer;
+        durations.push(duration);
+    }
+
+    Ok((output, Stats::from_samples(&mut durations)))
+}
+
+impl Stats {
+    pub fn from_samples(durations: &mut [Duration]) -> Self {
+        durations.sort();
+
+        let nanos: Vec<f64> = durations.iter()
+            .map(|duration| duration.as_nanos() as f64)
+            .collect();
+
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+        let variance = if nanos.len() > 1 {
+            nanos.iter()
+                .map(|x| (x - mean).powi(2))
+                .sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        let q1 = percentile(&nanos, 0.25);
+        let q3 = percentile(&nanos, 0.75);
+        let iqr = q3 - q1;
+
+        let outliers = nanos.iter()
+            .fold(Outliers::default(), |mut outliers, &x| {
+                if x < q1 - 3.0 * iqr {
+                    outliers.low_severe += 1;
+                } else if x < q1 - 1.5 * iqr {
+                    outliers.low_mild += 1;
+                } else if x > q3 + 3.0 * iqr {
+                    outliers.high_severe += 1;
+                } else if x > q3 + 1.5 * iqr {
+                    outliers.high_mild += 1;
+                }
+                outliers
+            });
+
+        Self {
+            samples: durations.len(),
+            min: durations[0],
+            median: Duration::from_nanos(percentile(&nanos, 0.5) as u64),
+            mean: Duration::from_nanos(mean as u64),
+            std_dev: Duration::from_nanos(variance.sqrt() as u64),
+            outliers,
+        }
+    }
+
+    pub fn print(&self) {
+        let ms = |duration: Duration| duration.as_nanos() as f64 / 1_000_000.0;
+
+        println!(
+            "min {:.3}ms, median {:.3}ms, mean {:.3}ms ± {:.3}ms ({} samples)",
+            ms(self.min),
+            ms(self.median),
+            ms(self.mean),
+            ms(self.std_dev),
+            self.samples,
+        );
+
+        let outliers = &self.outliers;
+        let total = outliers.low_severe + outliers.low_mild + outliers.high_mild + outliers.high_severe;
+        if total > 0 {
+            println!(
+                "{total} outliers: {} low severe, {} low mild, {} high mild, {} high severe",
+                outliers.low_severe,
+                outliers.low_mild,
+                outliers.high_mild,
+                outliers.high_severe,
+            );
+        }
+    }
+}
+
+// linear interpolation between closest ranks, expects sorted input
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[test]
+fn test_stats() {
+    let mut durations: Vec<Duration> = [10, 12, 11, 13, 12, 11, 12, 40, 1]
+        .into_iter()
+        .map(Duration::from_micros)
+        .collect();
+
+    let stats = Stats::from_samples(&mut durations);
+
+    assert_eq!(stats.samples, 9);
+    assert_eq!(stats.min, Duration::from_micros(1));
+    assert_eq!(stats.median, Duration::from_micros(12));
+    assert_eq!(stats.outliers, Outliers {
+        low_severe: 1,
+        low_mild: 0,
+        high_mild: 0,
+        high_severe: 1,
+    });
+}
+
+#[test]
+fn test_time_limit() {
+    // 3 warm up runs and 100 samples, plus the warm up time
+    assert_eq!(time_limit(Duration::from_secs(10), 100), Duration::from_millis(1_030_200));
+    assert_eq!(time_limit(Duration::from_secs(1), 0), Duration::from_millis(4_200));
+}
@@ -1,61 +1,499 @@
-use clap::{arg, value_parser, Arg, ArgGroup, Command};
+use std::{collections::{BTreeMap, BTreeSet}, env, path::PathBuf, time::Duration};
+
+use clap::{arg, error::ErrorKind, value_parser, Arg, ArgAction, ArgGroup, ArgMatches, Command};
+
+use crate::{client::{DEFAULT_BASE_URL, DEFAULT_SESSION_FILE, DEFAULT_USER_AGENT}, output::Format, solutions};
 
 /*
 command line interface for advent of code
-specify day number as an argument
--t flag to run with test input
--a flag to run all solutions
+specify days as an argument, either a single day or a list of days and ranges e.g. 3,5,7-9
+--part to only run one part
+-t flag to check solutions against their example inputs
+-a flag to run all registered solutions
+--year to choose which year's solutions to use, defaulting to the latest
+history subcommand to show recorded runtimes
+new subcommand to set up a new day from the template
+watch subcommand to rerun a day's tests and solutions whenever its source or input changes
+report subcommand to run everything and write a summary table, checking answers as --verify does
+fetch subcommand to download inputs, from --base-url (or AOC_BASE_URL) with the session token in AOC_SESSION or --session-file
+submit subcommand to solve a part and post its answer the same way, refusing answers already known to be wrong
+-b flag to benchmark solutions over many samples
+--timeout to limit how long each part can run, either for every day or per day
+--format to choose between text, json and ndjson output
+--verify to check answers against the accepted answers in answers/YEAR/
+--input to read a different input file, or - for stdin
+--alternates to run every input in inputs/YEAR/day_N/ and show a table of the results
+--jobs to run days in parallel, without timings
+--save-baseline and --compare to check runtimes against an earlier run, failing past --max-regression
+AOC_INPUT_DIR environment variable to look for inputs somewhere other than ./inputs (still in a directory per year)
 
 future:
 
 */
 
+const DEFAULT_INPUT_DIR: &str = "inputs";
+
 pub fn get_config() -> Config {
-    let matches = Command::new("AoC2024")
+    let mut command = Command::new("aoc")
+    .about("Advent of Code solutions, for every year in src/solutions")
     .arg(
         Arg::new("day")
-            .help("Which day's problem to solve")
+            .help("Which days' problems to solve, e.g. 5 or 3,5,7-9")
             .index(1)
-            .value_parser(value_parser!(u8).range(1..=25))
+            .value_parser(parse_days)
         )
     .arg(
         arg!(-a --all "Run all solutions")
     )
+    .arg(
+        arg!(-y --year <YEAR> "Which year's solutions to use, defaulting to the latest")
+            .value_parser(value_parser!(u16).range(2015..))
+            .global(true)
+    )
+    .arg(
+        arg!(-p --part <PART> "Only run this part")
+            .value_parser(value_parser!(u8).range(1..=2))
+    )
     .group(
         ArgGroup::new("mode")
             .args(&["day", "all"])
             .required(true)
     )
     .arg(
-        arg!(-t --test "Use test input file instead")
+        arg!(-t --test "Check solutions against the example inputs instead")
+    )
+    .arg(
+        arg!(-b --bench "Warm up and run each solution many times, reporting statistics")
+            .conflicts_with("test")
+    )
+    .arg(
+        arg!(--samples <N> "Number of samples to take in bench mode")
+            .value_parser(value_parser!(usize))
+            .default_value("100")
+            .requires("bench")
+    )
+    .arg(
+        arg!(--timeout <SECS> "Time limit for each part, or DAY=SECS to override a single day")
+            .value_parser(parse_timeout)
+            .action(ArgAction::Append)
+            .global(true)
+    )
+    .arg(
+        arg!(--verify "Check answers against answers/YEAR/day_N.txt, failing if any don't match")
+            .conflicts_with("test")
+    )
+    .arg(
+        arg!(-i --input <PATH> "Read the input from this file instead, or - for stdin")
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all(["test", "verify", "all"])
+    )
+    .arg(
+        arg!(--alternates "Run every input in inputs/YEAR/day_N/, checking answers/YEAR/day_N/ where they exist")
+            .conflicts_with_all(["test", "verify", "input", "bench"])
+    )
+    .arg(
+        arg!(-j --jobs <N> "Run this many days at once, leaving out runtimes as they aren't reliable")
+            .value_parser(value_parser!(usize))
+            .default_value("1")
+            .conflicts_with("bench")
+    )
+    .arg(
+        arg!(--"save-baseline" <PATH> "Save each part's runtime to this file, to --compare against later")
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all(["test", "alternates", "jobs"])
+    )
+    .arg(
+        arg!(--compare <PATH> "Compare runtimes against a baseline saved with --save-baseline")
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all(["test", "alternates", "jobs"])
+    )
+    .arg(
+        arg!(--"max-regression" <PERCENT> "Fail if any part is this much slower than the baseline")
+            .value_parser(value_parser!(f64))
+            .default_value("10")
+            .requires("compare")
     )
-    .get_matches();
+    .arg(
+        arg!(--format <FORMAT> "Output format")
+            .value_parser(Format::NAMES)
+            .default_value("text")
+    )
+    .subcommand(
+        Command::new("history")
+            .about("Show how recorded runtimes have changed over time")
+            .arg(
+                Arg::new("day")
+                    .help("Only show history for this day")
+                    .index(1)
+                    .value_parser(value_parser!(u8).range(1..=25))
+            )
+    )
+    .subcommand(
+        Command::new("new")
+            .about("Create a new day's module from the template, register it and create its input file")
+            .arg(
+                Arg::new("day")
+                    .help("Which day to create")
+                    .index(1)
+                    .required(true)
+                    .value_parser(value_parser!(u8).range(1..=25))
+            )
+            .arg(
+                arg!(--title <TITLE> "The puzzle's title")
+            )
+    )
+    .subcommand(
+        Command::new("watch")
+            .about("Rerun a day's tests and solutions whenever its source or input changes")
+            .arg(
+                Arg::new("day")
+                    .help("Which day to watch")
+                    .index(1)
+                    .required(true)
+                    .value_parser(value_parser!(u8).range(1..=25))
+            )
+    )
+    .subcommand(
+        Command::new("report")
+            .about("Run every registered solution and write a Markdown summary")
+            .arg(
+                arg!(--redact "Hide answers")
+            )
+            .arg(
+                arg!(-o --output <PATH> "Write the Markdown table to this file instead of stdout")
+                    .value_parser(value_parser!(PathBuf))
+            )
+            .arg(
+                arg!(--html <PATH> "Also write a self-contained HTML page")
+                    .value_parser(value_parser!(PathBuf))
+            )
+            .arg(
+                arg!(--readme "Rewrite the section of README.md between the report markers")
+            )
+    )
+    .subcommand(
+        Command::new("fetch")
+            .about("Download inputs that aren't in inputs/YEAR/ yet")
+            .arg(
+                Arg::new("day")
+                    .help("Which days' inputs to fetch, e.g. 5 or 3,5,7-9")
+                    .index(1)
+                    .required(true)
+                    .value_parser(parse_days)
+            )
+            .args(remote_args())
+    )
+    .subcommand(
+        Command::new("submit")
+            .about("Solve a part and submit its answer, keeping a ledger of guesses in answers/YEAR/guesses.tsv")
+            .arg(
+                Arg::new("day")
+                    .help("Which day to submit")
+                    .index(1)
+                    .required(true)
+                    .value_parser(value_parser!(u8).range(1..=25))
+            )
+            .arg(
+                Arg::new("part")
+                    .help("Which part to submit")
+                    .index(2)
+                    .required(true)
+                    .value_parser(value_parser!(u8).range(1..=2))
+            )
+            .args(remote_args())
+    )
+    .subcommand_negates_reqs(true)
+    .args_conflicts_with_subcommands(true);
+
+    let matches = command.get_matches_mut();
+
+    let year = matches.get_one::<u16>("year")
+        .cloned()
+        .unwrap_or_else(solutions::latest_year);
 
-    let runmode = match matches.get_one::<u8>("day").cloned() {
-        Some(day) => {
-            Runmode::One { 
-            day,
+    let mut timeouts = Timeouts::default();
+    for (day, limit) in matches.get_many::<(Option<u8>, Duration)>("timeout").into_iter().flatten() {
+        match day {
+            Some(day) => {
+                timeouts.per_day.insert(*day, *limit);
+            },
+            None => timeouts.default = *limit,
+        }
+    }
+
+    let runmode = match matches.subcommand() {
+        Some(("history", sub_matches)) => {
+            Runmode::History {
+                day: sub_matches.get_one::<u8>("day").cloned(),
+            }
+        },
+        Some(("new", sub_matches)) => {
+            Runmode::New {
+                day: sub_matches.get_one::<u8>("day").cloned().unwrap_or(1),
+                title: sub_matches.get_one::<String>("title").cloned(),
+            }
+        },
+        Some(("watch", sub_matches)) => {
+            Runmode::Watch {
+                day: sub_matches.get_one::<u8>("day").cloned().unwrap_or(1),
+            }
+        },
+        Some(("fetch", sub_matches)) => {
+            Runmode::Fetch {
+                days: sub_matches.get_one::<BTreeSet<u8>>("day").cloned().unwrap_or_default(),
+                remote: remote_options(sub_matches),
+            }
+        },
+        Some(("submit", sub_matches)) => {
+            Runmode::Submit {
+                day: sub_matches.get_one::<u8>("day").cloned().unwrap_or(1),
+                part: sub_matches.get_one::<u8>("part").cloned().unwrap_or(1),
+                remote: remote_options(sub_matches),
             }
         },
-        None => Runmode::All
+        Some(("report", sub_matches)) => {
+            Runmode::Report(ReportOptions {
+                redact: sub_matches.get_flag("redact"),
+                output: sub_matches.get_one::<PathBuf>("output").cloned(),
+                html: sub_matches.get_one::<PathBuf>("html").cloned(),
+                readme: sub_matches.get_flag("readme"),
+            })
+        },
+        _ => {
+            let days = matches.get_one::<BTreeSet<u8>>("day")
+                .cloned()
+                .unwrap_or_else(|| solutions::days(year).iter().map(|(day, _)| *day).collect());
+
+            let parts = match matches.get_one::<u8>("part").cloned() {
+                Some(part) => vec![part],
+                None => vec![1, 2],
+            };
+
+            let targets = days.into_iter()
+                .flat_map(|day| parts.iter().map(move |&part| Target { day, part }))
+                .collect();
+
+            Runmode::Solve { targets }
+        },
     };
 
+    if solutions::days(year).is_empty() && !matches!(runmode, Runmode::New { .. } | Runmode::History { .. } | Runmode::Fetch { .. }) {
+        command.error(ErrorKind::InvalidValue, format!("there are no solutions for {year} yet, `new` creates the first one"))
+            .exit();
+    }
+
+    let input = match matches.get_one::<PathBuf>("input") {
+        Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+        Some(path) => InputSource::File(path.clone()),
+        None => InputSource::Default,
+    };
+
+    if input != InputSource::Default && matches.get_one::<BTreeSet<u8>>("day").is_some_and(|days| days.len() > 1) {
+        command.error(ErrorKind::ArgumentConflict, "--input can only be used with a single day")
+            .exit();
+    }
+
+    let input_dir = env::var_os("AOC_INPUT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_INPUT_DIR))
+        .join(year.to_string());
+
+    // reports always check answers
+    let report = matches!(runmode, Runmode::Report(_));
+
     Config {
         runmode,
+        year,
         test: matches.get_flag("test"),
+        bench: matches.get_flag("bench"),
+        samples: matches.get_one::<usize>("samples").cloned().unwrap_or(100),
+        jobs: matches.get_one::<usize>("jobs").cloned().unwrap_or(1),
+        timeouts,
+        format: matches.get_one::<String>("format")
+            .and_then(|name| Format::from_name(name))
+            .unwrap_or(Format::Text),
+        verify: matches.get_flag("verify") || report,
+        input,
+        input_dir,
+        alternates: matches.get_flag("alternates"),
+        save_baseline: matches.get_one::<PathBuf>("save-baseline").cloned(),
+        compare: matches.get_one::<PathBuf>("compare").cloned(),
+        max_regression: matches.get_one::<f64>("max-regression").cloned().unwrap_or(10.0),
+    }
+}
+
+// for subcommands that talk to the puzzle site
+fn remote_args() -> [Arg; 3] {
+    [
+        arg!(--"base-url" <URL> "Where the puzzle site is, for testing against a local server (or AOC_BASE_URL)"),
+        arg!(--"session-file" <PATH> "File holding the session token, if AOC_SESSION isn't set (or AOC_SESSION_FILE)")
+            .value_parser(value_parser!(PathBuf)),
+        arg!(--"user-agent" <AGENT> "User-Agent sent with requests, best with your email or repo in it (or AOC_USER_AGENT)"),
+    ]
+}
+
+fn remote_options(matches: &ArgMatches) -> RemoteOptions {
+    RemoteOptions {
+        base_url: matches.get_one::<String>("base-url")
+            .cloned()
+            .or_else(|| env::var("AOC_BASE_URL").ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+        session_file: matches.get_one::<PathBuf>("session-file")
+            .cloned()
+            .or_else(|| env::var_os("AOC_SESSION_FILE").map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_SESSION_FILE)),
+        user_agent: matches.get_one::<String>("user-agent")
+            .cloned()
+            .or_else(|| env::var("AOC_USER_AGENT").ok())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.to_string()),
+    }
+}
+
+fn parse_days(arg: &str) -> Result<BTreeSet<u8>, String> {
+    let parse_day = |day: &str| -> Result<u8, String> {
+        day.trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or_else(|| format!("`{day}` isn't a day between 1 and 25"))
+    };
+
+    let mut days = BTreeSet::new();
+
+    for item in arg.split(',') {
+        match item.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (parse_day(first)?, parse_day(last)?);
+                if first > last {
+                    return Err(format!("`{item}` is an empty range"));
+                }
+                days.extend(first..=last);
+            },
+            None => {
+                days.insert(parse_day(item)?);
+            },
+        }
     }
+
+    Ok(days)
+}
+
+fn parse_timeout(arg: &str) -> Result<(Option<u8>, Duration), String> {
+    let (day, secs) = match arg.split_once('=') {
+        Some((day, secs)) => {
+            let day = day.parse::<u8>()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("`{day}` isn't a day between 1 and 25"))?;
+            (Some(day), secs)
+        },
+        None => (None, arg),
+    };
+
+    let secs: f64 = secs.parse()
+        .map_err(|_| format!("`{secs}` isn't a number of seconds"))?;
+
+    let limit = Duration::try_from_secs_f64(secs)
+        .map_err(|e| e.to_string())?;
+
+    Ok((day, limit))
 }
 
 pub struct Config {
     pub runmode: Runmode,
+    pub year: u16,
     pub test: bool,
+    pub bench: bool,
+    pub samples: usize,
+    // days run at once, 1 being sequential
+    pub jobs: usize,
+    pub timeouts: Timeouts,
+    pub format: Format,
+    pub verify: bool,
+    pub input: InputSource,
+    // this year's inputs, e.g. inputs/2023
+    pub input_dir: PathBuf,
+    pub alternates: bool,
+    pub save_baseline: Option<PathBuf>,
+    pub compare: Option<PathBuf>,
+    // percent
+    pub max_regression: f64,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum InputSource {
+    // inputs/YEAR/day_N.txt, or the same under wherever AOC_INPUT_DIR points
+    Default,
+    File(PathBuf),
+    Stdin,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub struct Target {
+    pub day: u8,
+    pub part: u8,
+}
+
+pub struct Timeouts {
+    pub default: Duration,
+    pub per_day: BTreeMap<u8, Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            default: Duration::from_secs(10),
+            per_day: BTreeMap::new(),
+        }
+    }
+}
+
+impl Timeouts {
+    pub fn for_day(&self, day: u8) -> Duration {
+        self.per_day.get(&day)
+            .cloned()
+            .unwrap_or(self.default)
+    }
 }
 
 pub enum Runmode {
-    One {
+    Solve {
+        targets: BTreeSet<Target>,
+    },
+    History {
+        day: Option<u8>,
+    },
+    New {
+        day: u8,
+        title: Option<String>,
+    },
+    Watch {
         day: u8,
     },
-    All,
+    Report(ReportOptions),
+    Fetch {
+        days: BTreeSet<u8>,
+        remote: RemoteOptions,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        remote: RemoteOptions,
+    },
+}
+
+pub struct RemoteOptions {
+    pub base_url: String,
+    pub session_file: PathBuf,
+    pub user_agent: String,
+}
+
+pub struct ReportOptions {
+    pub redact: bool,
+    // stdout if not set
+    pub output: Option<PathBuf>,
+    pub html: Option<PathBuf>,
+    pub readme: bool,
 }
 
 /*
@@ -75,7 +513,16 @@ questions
     yes that would be good
 4) can solutions run in parallel?
     probably not without messing up the timing
+    --jobs does, for correctness checks, and leaves the timings out
 5) should printing be progressive or all at once?
     progressive is nicer
 
-*/
\ No newline at end of file
+*/
+
+#[test]
+fn test_parse_days() {
+    assert_eq!(parse_days("5"), Ok(BTreeSet::from([5])));
+    assert_eq!(parse_days("3,5,7-9"), Ok(BTreeSet::from([3, 5, 7, 8, 9])));
+    assert_eq!(parse_days("9-7").map_err(|_| ()), Err(()));
+    assert_eq!(parse_days("0,26").map_err(|_| ()), Err(()));
+}
@@ -0,0 +1,194 @@
+use std::{env, fs::{read_to_string, write}, path::PathBuf, thread, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use crate::cli::RemoteOptions;
+
+/*
+HTTP client for the puzzle site, shared by the subcommands that talk to it.
+
+Requests go to `--base-url` (or AOC_BASE_URL), so everything can be tried against a local server
+without touching the real site, and carry the session token from a logged in browser's `session` cookie.
+The token comes from AOC_SESSION, or else the file at `--session-file` (or AOC_SESSION_FILE, default .session).
+
+To be polite, every request has a User-Agent saying where it came from (`--user-agent` or AOC_USER_AGENT,
+which should have a way to contact you), and requests are spaced at least `RATE_LIMIT` apart.
+The time of the last request is kept in .last_request next to the session file,
+so the spacing holds across runs too, e.g. a shell loop over days.
+*/
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_SESSION_FILE: &str = ".session";
+pub const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"), " (+", env!("CARGO_PKG_REPOSITORY"), ")");
+
+const RATE_LIMIT: Duration = Duration::from_secs(3);
+const TIMEOUT: Duration = Duration::from_secs(30);
+const LAST_REQUEST_FILE: &str = ".last_request";
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    rate_limit: Duration,
+    last_request: Option<SystemTime>,
+    // where `last_request` is shared with other runs
+    last_request_path: Option<PathBuf>,
+}
+
+impl Client {
+    pub fn new(options: &RemoteOptions) -> Result<Self, String> {
+        let last_request_path = options.session_file.with_file_name(LAST_REQUEST_FILE);
+
+        let mut client = Self::with_session(&options.base_url, session(options)?)
+            .user_agent(&options.user_agent);
+        client.last_request = read_to_string(&last_request_path).ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+        client.last_request_path = Some(last_request_path);
+
+        Ok(client)
+    }
+
+    pub fn with_session(base_url: &str, session: String) -> Self {
+        Self {
+            agent: Self::agent(DEFAULT_USER_AGENT),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            rate_limit: RATE_LIMIT,
+            last_request: None,
+            last_request_path: None,
+        }
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.agent = Self::agent(user_agent);
+        self
+    }
+
+    fn agent(user_agent: &str) -> ureq::Agent {
+        ureq::AgentBuilder::new()
+            .user_agent(user_agent)
+            .timeout(TIMEOUT)
+            .build()
+    }
+
+    pub fn rate_limit(mut self, rate_limit: Duration) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    pub fn get(&mut self, path: &str) -> Result<String, String> {
+        let request = self.request("GET", path);
+        Self::body(path, request.call())
+    }
+
+    pub fn post_form(&mut self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        let request = self.request("POST", path);
+        Self::body(path, request.send_form(form))
+    }
+
+    // waits out the rate limit first, so callers don't have to
+    fn request(&mut self, method: &str, path: &str) -> ureq::Request {
+        if let Some(last) = self.last_request {
+            // a clock that went backwards counts as just now
+            let elapsed = last.elapsed().unwrap_or_default();
+            thread::sleep(self.rate_limit.saturating_sub(elapsed));
+        }
+
+        let now = SystemTime::now();
+        self.last_request = Some(now);
+
+        // losing this only loses the spacing for the next run, which isn't worth failing the request over
+        if let Some(path) = &self.last_request_path {
+            let millis = now.duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+            let _ = write(path, format!("{millis}\n"));
+        }
+
+        self.agent.request(method, &format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+    }
+
+    fn body(path: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String, String> {
+        match response {
+            Ok(response) => response.into_string()
+                .map_err(|e| format!("Couldn't read the response to {path}: {e}")),
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                let hint = match status {
+                    400 | 401 | 500 => " (is the session token still valid?)",
+                    404 => " (is the puzzle out yet?)",
+                    _ => "",
+                };
+
+                Err(format!("{path} returned {status}{hint}: {}", body.lines().next().unwrap_or_default()))
+            },
+            Err(ureq::Error::Transport(error)) => Err(format!("Couldn't request {path}: {error}")),
+        }
+    }
+}
+
+fn session(options: &RemoteOptions) -> Result<String, String> {
+    if let Some(session) = env::var("AOC_SESSION").ok().filter(|session| !session.trim().is_empty()) {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = &options.session_file;
+    let session = read_to_string(path)
+        .map_err(|e| format!("Couldn't read the session token from {}, set AOC_SESSION or --session-file: {e}", path.display()))?;
+
+    match session.trim() {
+        "" => Err(format!("{} is empty", path.display())),
+        session => Ok(session.to_string()),
+    }
+}
+
+// what `serve_once` was sent
+#[cfg(test)]
+pub struct Request {
+    pub line: String,
+    pub headers: Vec<String>,
+    pub body: String,
+}
+
+// a stand-in for the puzzle site that answers the first request with `page`,
+// anything after that gets refused once the listener is gone
+#[cfg(test)]
+pub fn serve_once(page: &'static str) -> (String, thread::JoinHandle<Request>) {
+    use std::{io::{BufRead, BufReader, Read, Write}, net::TcpListener};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+    let server = thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+
+        let mut headers = Vec::new();
+        loop {
+            let mut header = String::new();
+            reader.read_line(&mut header).unwrap();
+            if header.trim().is_empty() {
+                break;
+            }
+            headers.push(header.trim().to_string());
+        }
+
+        let length = headers.iter()
+            .filter_map(|header| header.split_once(':'))
+            .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+            .map_or(0, |(_, value)| value.trim().parse().unwrap());
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).unwrap();
+
+        write!(reader.get_mut(), "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{page}", page.len()).unwrap();
+
+        Request {
+            line: line.trim().to_string(),
+            headers,
+            body: String::from_utf8(body).unwrap(),
+        }
+    });
+
+    (base_url, server)
+}
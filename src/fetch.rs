@@ -0,0 +1,106 @@
+use std::{collections::BTreeSet, fs::{create_dir_all, metadata, rename, write}, path::Path};
+
+use crate::{cli::RemoteOptions, client::Client};
+
+/*
+`fetch` subcommand, downloads days' inputs to inputs/YEAR/day_N.txt.
+
+An input that's already there is never fetched again, as inputs don't change.
+The empty file `new` leaves behind doesn't count.
+The client is only made once a day needs downloading, so days that are all cached don't need a session token.
+Inputs are written to a .part file first, so an interrupted download doesn't look like an input.
+*/
+
+#[derive(Debug, PartialEq)]
+pub enum Fetched {
+    // bytes downloaded
+    Downloaded(usize),
+    Cached,
+}
+
+pub fn fetch_days(remote: &RemoteOptions, year: u16, days: &BTreeSet<u8>, input_dir: &Path) -> Result<(), String> {
+    let mut client = None;
+    let mut failed = 0;
+
+    for &day in days {
+        let path = input_dir.join(format!("day_{day}.txt"));
+
+        if cached(&path) {
+            println!("Day {day}: already in {}", path.display());
+            continue;
+        }
+
+        let client = match &mut client {
+            Some(client) => client,
+            None => client.insert(Client::new(remote)?),
+        };
+
+        match fetch(client, year, day, input_dir) {
+            Ok(Fetched::Downloaded(bytes)) => println!("Day {day}: fetched {bytes} bytes to {}", path.display()),
+            Ok(Fetched::Cached) => println!("Day {day}: already in {}", path.display()),
+            Err(error) => {
+                println!("Day {day}: {error}");
+                failed += 1;
+            },
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        1 => Err("1 day couldn't be fetched".to_string()),
+        failed => Err(format!("{failed} days couldn't be fetched")),
+    }
+}
+
+pub fn fetch(client: &mut Client, year: u16, day: u8, input_dir: &Path) -> Result<Fetched, String> {
+    let path = input_dir.join(format!("day_{day}.txt"));
+
+    if cached(&path) {
+        return Ok(Fetched::Cached);
+    }
+
+    let input = client.get(&format!("/{year}/day/{day}/input"))?;
+
+    create_dir_all(input_dir)
+        .map_err(|e| format!("Couldn't create {}: {e}", input_dir.display()))?;
+
+    let part_path = path.with_extension("txt.part");
+    write(&part_path, &input)
+        .map_err(|e| format!("Couldn't write {}: {e}", part_path.display()))?;
+    rename(&part_path, &path)
+        .map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+
+    Ok(Fetched::Downloaded(input.len()))
+}
+
+fn cached(path: &Path) -> bool {
+    metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+#[test]
+fn test_fetch() {
+    use std::time::Duration;
+
+    let (base_url, server) = crate::client::serve_once("1abc2\r\npqr3stu8vwx\r\n");
+
+    let input_dir = std::env::temp_dir().join(format!("aoc-fetch-test-{}", std::process::id()));
+    let mut client = Client::with_session(&base_url, "abc123".to_string())
+        .rate_limit(Duration::ZERO);
+
+    assert_eq!(fetch(&mut client, 2023, 1, &input_dir), Ok(Fetched::Downloaded(20)));
+
+    let request = server.join().unwrap();
+    assert_eq!(request.line, "GET /2023/day/1/input HTTP/1.1");
+    assert!(request.headers.iter().any(|header| header.to_lowercase() == "cookie: session=abc123"));
+    assert!(request.headers.iter().any(|header| header.to_lowercase().starts_with("user-agent: aoc_2023/")));
+
+    // kept exactly as it came, the loader normalizes it
+    assert_eq!(std::fs::read_to_string(input_dir.join("day_1.txt")).unwrap(), "1abc2\r\npqr3stu8vwx\r\n");
+    assert_eq!(fetch(&mut client, 2023, 1, &input_dir), Ok(Fetched::Cached));
+
+    // nothing is listening any more
+    assert!(fetch(&mut client, 2023, 2, &input_dir).is_err());
+    assert!(!input_dir.join("day_2.txt").exists());
+
+    let _ = std::fs::remove_dir_all(&input_dir);
+}
@@ -0,0 +1,246 @@
+use std::{collections::BTreeMap, fs::{read_to_string, OpenOptions}, io::Write, process::Command, str::FromStr, sync::OnceLock, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+/*
+Runtime history, so improvements (and regressions) can be tracked over time.
+
+Every successful timed solve is appended to a tab separated file, one entry per line:
+year, day, part, duration in nanoseconds, answer hash, git commit, unix timestamp
+
+Lines written before the year was added have the other six fields only, and are all from 2023.
+
+The answer is hashed rather than stored so the file can be shared without giving answers away,
+while still showing when a change to a solution changed its output.
+*/
+
+const HISTORY_PATH: &str = "runtimes.tsv";
+// the year of the lines without one
+const YEARLESS: u16 = 2023;
+
+pub struct Entry {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub duration: Duration,
+    pub answer_hash: u64,
+    pub commit: String,
+    pub timestamp: u64,
+}
+
+impl Entry {
+    pub fn new(year: u16, day: u8, part: u8, answer: &str, duration: Duration) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            year,
+            day,
+            part,
+            duration,
+            answer_hash: hash_answer(answer),
+            commit: git_commit().to_string(),
+            timestamp,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{:016x}\t{}\t{}",
+            self.year,
+            self.day,
+            self.part,
+            self.duration.as_nanos(),
+            self.answer_hash,
+            self.commit,
+            self.timestamp,
+        )
+    }
+}
+
+impl FromStr for Entry {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |s: &str| -> Option<Self> {
+            let mut fields = s.split('\t');
+            let year = match s.split('\t').count() {
+                6 => YEARLESS,
+                _ => fields.next()?.parse().ok()?,
+            };
+
+            let entry = Self {
+                year,
+                day: fields.next()?.parse().ok()?,
+                part: fields.next()?.parse().ok()?,
+                duration: Duration::from_nanos(fields.next()?.parse().ok()?),
+                answer_hash: u64::from_str_radix(fields.next()?, 16).ok()?,
+                commit: fields.next()?.to_string(),
+                timestamp: fields.next()?.parse().ok()?,
+            };
+
+            Some(entry)
+        };
+
+        parse(s).ok_or_else(|| {
+            format!("Couldn't parse history entry from line `{s}`")
+        })
+    }
+}
+
+pub fn record(entry: &Entry) -> Result<(), std::io::Error> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)?;
+
+    writeln!(file, "{}", entry.to_line())
+}
+
+pub fn load() -> Result<Vec<Entry>, String> {
+    let contents = match read_to_string(HISTORY_PATH) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    parse_entries(&contents)
+}
+
+fn parse_entries(contents: &str) -> Result<Vec<Entry>, String> {
+    contents.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse())
+        .collect()
+}
+
+pub fn print_history(year: u16, day: Option<u8>) {
+    let entries = match load() {
+        Ok(entries) => entries,
+        Err(error) => {
+            println!("Couldn't read runtime history:");
+            println!("{error}");
+            return;
+        },
+    };
+
+    // entries are appended in order, so each group is already chronological
+    let mut by_part: BTreeMap<(u8, u8), Vec<&Entry>> = BTreeMap::new();
+    let entries = entries.iter()
+        .filter(|entry| entry.year == year)
+        .filter(|entry| day.is_none_or(|day| day == entry.day));
+
+    for entry in entries {
+        by_part.entry((entry.day, entry.part))
+            .or_default()
+            .push(entry);
+    }
+
+    if by_part.is_empty() {
+        println!("No runtimes recorded for {year} yet");
+        return;
+    }
+
+    for ((day, part), entries) in by_part {
+        println!("Day {day} Part {}", if part == 1 { "One" } else { "Two" });
+
+        let mut prev: Option<&Entry> = None;
+        for entry in entries {
+            let runtime = entry.duration.as_micros() as f32 / 1000.0;
+            let mut line = format!(
+                "  {}  {:<13} {:>10.3}ms",
+                format_timestamp(entry.timestamp),
+                entry.commit,
+                runtime,
+            );
+
+            if let Some(prev) = prev {
+                let change = entry.duration.as_secs_f64() / prev.duration.as_secs_f64() - 1.0;
+                line += &format!("  {:>+7.1}%", change * 100.0);
+
+                if entry.answer_hash != prev.answer_hash {
+                    line += "  (answer changed)";
+                }
+            }
+
+            println!("{line}");
+            prev = Some(entry);
+        }
+    }
+}
+
+pub fn git_commit() -> &'static str {
+    // only ask git once per run
+    static COMMIT: OnceLock<String> = OnceLock::new();
+
+    COMMIT.get_or_init(|| {
+        Command::new("git")
+            .args(["describe", "--always", "--dirty", "--abbrev=7"])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|commit| commit.trim().to_string())
+            .unwrap_or_else(|| "unknown".to_string())
+    })
+}
+
+// FNV-1a, std's hasher isn't guaranteed to be stable between releases
+fn hash_answer(answer: &str) -> u64 {
+    answer.bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+fn format_timestamp(timestamp: u64) -> String {
+    // days since epoch to a civil date, see http://howardhinnant.github.io/date_algorithms.html
+    let days = (timestamp / 86400) as i64;
+    let secs = timestamp % 86400;
+
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = doy - (153 * mp + 2) / 5 + 1;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 };
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+
+    format!("{y:04}-{m:02}-{d:02} {:02}:{:02}", secs / 3600, secs % 3600 / 60)
+}
+
+#[test]
+fn test_entry_round_trip() {
+    let entry = Entry {
+        year: 2023,
+        day: 3,
+        part: 2,
+        duration: Duration::from_nanos(2_213_456),
+        answer_hash: hash_answer("467835"),
+        commit: "849741c-dirty".to_string(),
+        timestamp: 1_701_388_800,
+    };
+
+    let parsed: Entry = entry.to_line().parse().unwrap();
+    assert_eq!(parsed.to_line(), entry.to_line());
+}
+
+#[test]
+fn test_parse_entries() {
+    let contents = "3\t2\t2213456\t0000000000000001\t849741c\t1701388800\n\
+        2024\t1\t1\t1000\t0000000000000002\t1a2b3c4\t1733011200\n";
+    let entries = parse_entries(contents).unwrap();
+
+    let keys: Vec<_> = entries.iter()
+        .map(|entry| (entry.year, entry.day, entry.part))
+        .collect();
+    assert_eq!(keys, [(2023, 3, 2), (2024, 1, 1)]);
+}
+
+#[test]
+fn test_format_timestamp() {
+    assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+    assert_eq!(format_timestamp(1_701_388_800 + 5 * 3600 + 30 * 60), "2023-12-01 05:30");
+}
@@ -0,0 +1,34 @@
+use std::{fs::read_to_string, io::{self, stdin}, path::Path};
+
+/*
+Shared input loading, so every solution sees the same thing whatever the file looked like:
+LF line endings, and exactly one trailing newline (as downloaded inputs have).
+*/
+
+pub fn load(path: &Path) -> Result<String, io::Error> {
+    read_to_string(path).map(|raw| normalize(&raw))
+}
+
+pub fn load_stdin() -> Result<String, io::Error> {
+    io::read_to_string(stdin()).map(|raw| normalize(&raw))
+}
+
+pub fn normalize(raw: &str) -> String {
+    let mut input = raw.replace("\r\n", "\n");
+
+    let len = input.trim_end_matches('\n').len();
+    input.truncate(len);
+
+    if !input.is_empty() {
+        input.push('\n');
+    }
+
+    input
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(normalize("a\r\nb\r\n\r\n"), "a\nb\n");
+    assert_eq!(normalize("a\nb"), "a\nb\n");
+    assert_eq!(normalize("\n\n"), "");
+}
@@ -1,3 +1,18 @@
+pub mod allocs;
+pub mod answers;
+pub mod baseline;
+pub mod bench;
 pub mod cli;
+pub mod client;
+pub mod fetch;
+pub mod history;
+pub mod input;
+pub mod output;
 pub mod solutions;
-pub mod runner;
\ No newline at end of file
+pub mod submit;
+pub mod progress;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod watch;
+pub mod worker;
\ No newline at end of file
@@ -1,7 +1,9 @@
+use std::process::ExitCode;
+
 use aoc_2023::{cli::get_config, runner::run};
 
-fn main() {
+fn main() -> ExitCode {
     let config = get_config();
-    run(config);
+    run(config)
 }
 
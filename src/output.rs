@@ -0,0 +1,594 @@
+use std::{fmt::Write, io::{stdout, IsTerminal}, process::ExitCode, time::Duration};
+
+use crate::{allocs::{format_bytes, Allocs}, answers::Verdict, baseline::Change, bench::Stats, cli::{Config, Runmode}, progress::{colour_enabled, paint, Colour, Live}, solutions};
+
+/*
+Every part the runner attempts ends up as a `Record`, which gets printed in one of three formats:
+text for people, ndjson for streaming into scripts (one record per line, as soon as the part finishes),
+or json for a single array once everything has run.
+
+When running alternate inputs, text output is a table of inputs × parts printed once each day is done.
+On a terminal, text output is instead a table with a row per part and a live progress line, see progress.rs.
+*/
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    Text,
+    Json,
+    Ndjson,
+}
+
+impl Format {
+    pub const NAMES: [&str; 3] = ["text", "json", "ndjson"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            "ndjson" => Some(Format::Ndjson),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Status {
+    Ok,
+    Err,
+    Unimplemented,
+    Timeout,
+    Panic,
+    Skipped,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Err => "err",
+            Status::Unimplemented => "unimplemented",
+            Status::Timeout => "timeout",
+            Status::Panic => "panic",
+            Status::Skipped => "skipped",
+        }
+    }
+}
+
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    // name of the alternate input, if this wasn't the usual one
+    pub input: Option<String>,
+    pub status: Status,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    // only set for errors returned by solutions
+    pub error_kind: Option<&'static str>,
+    pub error_position: Option<(usize, usize)>,
+    pub duration: Option<Duration>,
+    // the part of `duration` spent parsing, for solutions with a separate parse step
+    pub parse_duration: Option<Duration>,
+    // the whole parse, when one parse was shared between parts and `parse_duration` is this part's share
+    pub shared_parse: Option<Duration>,
+    pub bench: Option<Stats>,
+    // only counted with the count-allocs feature
+    pub allocs: Option<Allocs>,
+    pub verdict: Option<Verdict>,
+    // against the --compare baseline
+    pub change: Option<Change>,
+}
+
+impl Record {
+    pub fn ok(day: u8, part: u8, answer: String, duration: Option<Duration>) -> Self {
+        Self {
+            day,
+            part,
+            input: None,
+            status: Status::Ok,
+            answer: Some(answer),
+            error: None,
+            error_kind: None,
+            error_position: None,
+            duration,
+            parse_duration: None,
+            shared_parse: None,
+            bench: None,
+            allocs: None,
+            verdict: None,
+            change: None,
+        }
+    }
+
+    // how long the part would have taken on its own, with the whole of a shared parse,
+    // so history and baselines don't depend on which parts were run
+    pub fn standalone_duration(&self) -> Option<Duration> {
+        let duration = self.duration?;
+
+        match (self.shared_parse, self.parse_duration) {
+            (Some(shared), Some(share)) => Some(duration.saturating_sub(share) + shared),
+            _ => Some(duration),
+        }
+    }
+
+    pub fn failed(day: u8, part: u8, status: Status, error: String) -> Self {
+        Self {
+            day,
+            part,
+            input: None,
+            status,
+            answer: None,
+            error: Some(error),
+            error_kind: None,
+            error_position: None,
+            duration: None,
+            parse_duration: None,
+            shared_parse: None,
+            bench: None,
+            allocs: None,
+            verdict: None,
+            change: None,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"day\":{},\"part\":{}", self.day, self.part);
+
+        if let Some(input) = &self.input {
+            let _ = write!(json, ",\"input\":{}", json_string(input));
+        }
+
+        let _ = write!(
+            json,
+            ",\"status\":{},\"answer\":{},\"error\":{},\"duration_ns\":{}",
+            json_string(self.status.name()),
+            self.answer.as_deref().map_or("null".to_string(), json_string),
+            self.error.as_deref().map_or("null".to_string(), json_string),
+            self.duration.map_or("null".to_string(), |duration| duration.as_nanos().to_string()),
+        );
+
+        if let Some(parse) = self.parse_duration {
+            let _ = write!(json, ",\"parse_ns\":{}", parse.as_nanos());
+        }
+
+        if self.shared_parse.is_some() {
+            json.push_str(",\"parse_amortized\":true");
+        }
+
+        if let Some(kind) = self.error_kind {
+            let _ = write!(json, ",\"error_kind\":{}", json_string(kind));
+        }
+
+        if let Some((line, column)) = self.error_position {
+            let _ = write!(json, ",\"error_line\":{line},\"error_column\":{column}");
+        }
+
+        if let Some(stats) = &self.bench {
+            let o = &stats.outliers;
+            let _ = write!(
+                json,
+                ",\"bench\":{{\"samples\":{},\"min_ns\":{},\"median_ns\":{},\"mean_ns\":{},\"std_dev_ns\":{},\"outliers\":{}}}",
+                stats.samples,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.std_dev.as_nanos(),
+                o.low_severe + o.low_mild + o.high_mild + o.high_severe,
+            );
+        }
+
+        if let Some(allocs) = &self.allocs {
+            let _ = write!(
+                json,
+                ",\"allocs\":{{\"count\":{},\"bytes\":{},\"peak_bytes\":{}}}",
+                allocs.count,
+                allocs.bytes,
+                allocs.peak,
+            );
+        }
+
+        if let Some(verdict) = &self.verdict {
+            let _ = write!(json, ",\"verdict\":{}", json_string(verdict.name()));
+
+            if let Verdict::Wrong { expected } = verdict {
+                let _ = write!(json, ",\"expected\":{}", json_string(expected));
+            }
+        }
+
+        if let Some(change) = &self.change {
+            let _ = write!(json, ",\"change\":{}", json_string(change.name()));
+
+            if let Some(percent) = change.percent() {
+                let _ = write!(json, ",\"change_percent\":{percent:.1}");
+            }
+        }
+
+        json.push('}');
+        json
+    }
+
+    // a row of the live table, lined up with the header in `Output::new`
+    fn live_row(&self, test: bool, colour: bool) -> String {
+        let (name, status_colour) = match self.status {
+            Status::Ok if test => ("passed", Colour::Green),
+            Status::Ok => ("ok", Colour::Green),
+            Status::Err | Status::Panic => (self.status.name(), Colour::Red),
+            Status::Timeout => (self.status.name(), Colour::Yellow),
+            Status::Unimplemented | Status::Skipped => (self.status.name(), Colour::Dim),
+        };
+        let status = paint(&format!("{name:<13}"), status_colour, colour);
+
+        let time = self.duration
+            .map(|duration| format!("{:.3}ms", duration.as_micros() as f32 / 1000.0))
+            .unwrap_or_default();
+
+        let answer = match self.status {
+            Status::Ok => self.answer.clone().unwrap_or_default(),
+            _ => self.error.as_deref()
+                .and_then(|error| error.lines().next())
+                .unwrap_or_default()
+                .to_string(),
+        };
+
+        let mut row = format!("{:>3}  {:>4}  {status}  {time:>10}  {answer}", self.day, self.part);
+
+        match &self.verdict {
+            Some(Verdict::Correct) => row += &paint("  ✓", Colour::Green, colour),
+            Some(Verdict::Wrong { expected }) => row += &paint(&format!("  ✗ expected {expected}"), Colour::Red, colour),
+            _ => {},
+        }
+
+        match &self.change {
+            Some(Change::Faster(percent)) => row += &paint(&format!("  {percent:+.1}%"), Colour::Green, colour),
+            Some(Change::Slower(percent)) => row += &paint(&format!("  {percent:+.1}%"), Colour::Red, colour),
+            Some(Change::Unchanged(percent)) => row += &paint(&format!("  {percent:+.1}%"), Colour::Dim, colour),
+            _ => {},
+        }
+
+        row
+    }
+
+    // short summary for a table cell
+    fn cell(&self) -> String {
+        let mut cell = match self.status {
+            Status::Ok => {
+                let runtime = self.duration.unwrap_or_default().as_micros() as f32 / 1000.0;
+                format!("{} ({:.3}ms)", self.answer.as_deref().unwrap_or_default(), runtime)
+            },
+            status => {
+                let mut error = self.error.as_deref()
+                    .and_then(|error| error.lines().next())
+                    .unwrap_or_default()
+                    .to_string();
+                if error.chars().count() > 40 {
+                    error = error.chars().take(39).collect::<String>() + "…";
+                }
+                format!("{}: {error}", status.name())
+            },
+        };
+
+        match &self.verdict {
+            Some(Verdict::Correct) => cell += " ✓",
+            Some(Verdict::Wrong { expected }) => cell += &format!(" ✗ expected {expected}"),
+            _ => {},
+        }
+
+        cell
+    }
+
+    fn print_text(&self, test: bool) {
+        println!("Part {}", if self.part == 1 { "One" } else { "Two" });
+
+        let error = self.error.as_deref().unwrap_or_default();
+
+        match self.status {
+            Status::Ok if test => println!("Passed"),
+            Status::Ok => {
+                if let Some(stats) = &self.bench {
+                    stats.print();
+                    println!("answer:");
+                } else if let Some(duration) = self.duration {
+                    let ms = |duration: Duration| duration.as_micros() as f32 / 1000.0;
+
+                    match self.parse_duration {
+                        Some(parse) => println!(
+                            "Solved in {:.3}ms (parse {:.3}ms{}, solve {:.3}ms), answer:",
+                            ms(duration),
+                            ms(parse),
+                            if self.shared_parse.is_some() { " amortized" } else { "" },
+                            ms(duration.saturating_sub(parse)),
+                        ),
+                        None => println!("Solved in {:.3}ms, answer:", ms(duration)),
+                    }
+                } else {
+                    // runtimes are left out of parallel runs
+                    println!("Solved, answer:");
+                }
+                println!("{}", self.answer.as_deref().unwrap_or_default());
+                if let Some(allocs) = &self.allocs {
+                    println!(
+                        "{} allocation{}, {} allocated, {} peak",
+                        allocs.count,
+                        if allocs.count == 1 { "" } else { "s" },
+                        format_bytes(allocs.bytes),
+                        format_bytes(allocs.peak),
+                    );
+                }
+            },
+            Status::Err | Status::Unimplemented => {
+                println!("Failed with error:");
+                println!("{error}");
+            },
+            Status::Timeout => println!("Failed, {error}"),
+            Status::Panic => {
+                println!("Failed with panic:");
+                println!("{error}");
+            },
+            Status::Skipped => println!("Skipped, {error}"),
+        }
+
+        match &self.verdict {
+            Some(Verdict::Correct) => println!("Correct"),
+            Some(Verdict::Wrong { expected }) => println!("Wrong, expected `{expected}`"),
+            Some(Verdict::Unknown) => println!("Unknown, no accepted answer yet"),
+            None => {},
+        }
+
+        match &self.change {
+            Some(Change::Faster(percent)) => println!("Faster than the baseline ({percent:+.1}%)"),
+            Some(Change::Slower(percent)) => println!("Slower than the baseline ({percent:+.1}%)"),
+            Some(Change::Unchanged(percent)) => println!("Unchanged from the baseline ({percent:+.1}%)"),
+            Some(Change::New) => println!("Not in the baseline"),
+            None => {},
+        }
+    }
+}
+
+pub struct Output {
+    format: Format,
+    test: bool,
+    matrix: bool,
+    // keeps every record without printing anything, for the report
+    collect: bool,
+    records: Vec<Record>,
+    day_records: Vec<Record>,
+    wrong: usize,
+    // parts whose examples didn't all pass in test mode
+    failed: usize,
+    max_regression: f64,
+    regressed: usize,
+    // only on a terminal, and not for benchmarks, whose statistics need more than a row
+    live: Option<Live>,
+    colour: bool,
+    // for day titles
+    year: u16,
+}
+
+impl Output {
+    pub fn new(config: &Config) -> Self {
+        let live = config.format == Format::Text
+            && matches!(config.runmode, Runmode::Solve { .. })
+            && !config.alternates
+            && !config.bench
+            && config.jobs <= 1
+            && stdout().is_terminal();
+
+        Self {
+            format: config.format,
+            test: config.test,
+            matrix: config.alternates,
+            collect: matches!(config.runmode, Runmode::Report(_)),
+            records: Vec::new(),
+            day_records: Vec::new(),
+            wrong: 0,
+            failed: 0,
+            max_regression: config.max_regression,
+            regressed: 0,
+            live: live.then(|| Live::start(&format!("{:>3}  {:>4}  {:<13}  {:>10}  Answer", "Day", "Part", "Status", "Time"))),
+            colour: colour_enabled(),
+            year: config.year,
+        }
+    }
+
+    pub fn start_day(&self, day: u8) {
+        if self.format == Format::Text && !self.collect && self.live.is_none() {
+            match solutions::get(self.year, day) {
+                Some(solution) => println!("Day {day}: {}", solution.title),
+                None => println!("Day {day}"),
+            }
+        }
+    }
+
+    pub fn emit(&mut self, record: Record) {
+        if let Some(Verdict::Wrong { .. }) = record.verdict {
+            self.wrong += 1;
+        }
+
+        if self.test && !matches!(record.status, Status::Ok | Status::Skipped) {
+            self.failed += 1;
+        }
+
+        if let Some(Change::Slower(percent)) = record.change
+            && percent > self.max_regression
+        {
+            self.regressed += 1;
+        }
+
+        if let Some(live) = &self.live {
+            live.row(&record.live_row(self.test, self.colour));
+            return;
+        }
+
+        match self.format {
+            _ if self.collect => self.records.push(record),
+            Format::Text if self.matrix => self.day_records.push(record),
+            Format::Text => record.print_text(self.test),
+            Format::Ndjson => println!("{}", record.to_json()),
+            Format::Json => self.records.push(record),
+        }
+    }
+
+    pub fn end_day(&mut self) {
+        if self.day_records.is_empty() {
+            return;
+        }
+
+        let records = std::mem::take(&mut self.day_records);
+
+        let mut inputs: Vec<&str> = Vec::new();
+        let mut parts: Vec<u8> = Vec::new();
+        for record in &records {
+            let input = record.input.as_deref().unwrap_or_default();
+            if !inputs.contains(&input) {
+                inputs.push(input);
+            }
+            if !parts.contains(&record.part) {
+                parts.push(record.part);
+            }
+        }
+
+        let cell = |input: &str, part: u8| -> String {
+            records.iter()
+                .find(|record| record.part == part && record.input.as_deref().unwrap_or_default() == input)
+                .map(|record| record.cell())
+                .unwrap_or_default()
+        };
+
+        let header: Vec<String> = parts.iter()
+            .map(|&part| format!("Part {}", if part == 1 { "One" } else { "Two" }))
+            .collect();
+
+        let rows: Vec<Vec<String>> = inputs.iter()
+            .map(|input| parts.iter().map(|&part| cell(input, part)).collect())
+            .collect();
+
+        let input_width = inputs.iter()
+            .map(|input| input.chars().count())
+            .chain(["input".len()])
+            .max()
+            .unwrap_or_default();
+
+        let widths: Vec<usize> = (0..parts.len())
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .chain([header[col].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect();
+
+        let print_row = |name: &str, cells: &[String]| {
+            let mut line = format!("{name:<input_width$}");
+            for (cell, width) in cells.iter().zip(&widths) {
+                line += &format!("  {cell:<width$}");
+            }
+            println!("{}", line.trim_end());
+        };
+
+        print_row("input", &header);
+        for (input, row) in inputs.iter().zip(&rows) {
+            print_row(input, row);
+        }
+    }
+
+    // everything emitted so far, for report mode
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    // fails the process if any answer didn't match its accepted answer, any part failed its examples,
+    // or any part regressed too far
+    pub fn finish(mut self) -> ExitCode {
+        // stops the spinner before anything else gets printed
+        self.live.take();
+
+        if self.format == Format::Json && !self.collect {
+            let records: Vec<String> = self.records.iter()
+                .map(|record| record.to_json())
+                .collect();
+
+            println!("[{}]", records.join(","));
+        }
+
+        if self.wrong > 0 {
+            eprintln!("{} answer(s) didn't match the accepted answer", self.wrong);
+        }
+
+        if self.failed > 0 {
+            eprintln!("{} part(s) failed their examples", self.failed);
+        }
+
+        if self.regressed > 0 {
+            eprintln!("{} part(s) got more than {}% slower than the baseline", self.regressed, self.max_regression);
+        }
+
+        if self.wrong > 0 || self.failed > 0 || self.regressed > 0 {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            },
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+#[test]
+fn test_json_string() {
+    assert_eq!(json_string("plain"), "\"plain\"");
+    assert_eq!(json_string("a \"quote\"\n\\"), "\"a \\\"quote\\\"\\n\\\\\"");
+    assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+}
+
+#[test]
+fn test_record_to_json() {
+    let record = Record::ok(3, 1, "4361".to_string(), Some(Duration::from_nanos(1500)));
+    assert_eq!(
+        record.to_json(),
+        r#"{"day":3,"part":1,"status":"ok","answer":"4361","error":null,"duration_ns":1500}"#
+    );
+
+    let mut record = Record::ok(8, 1, "12".to_string(), Some(Duration::from_nanos(1500)));
+    record.parse_duration = Some(Duration::from_nanos(1000));
+    assert_eq!(
+        record.to_json(),
+        r#"{"day":8,"part":1,"status":"ok","answer":"12","error":null,"duration_ns":1500,"parse_ns":1000}"#
+    );
+
+    let record = Record::failed(5, 2, Status::Unimplemented, "Unimplemented".to_string());
+    assert_eq!(
+        record.to_json(),
+        r#"{"day":5,"part":2,"status":"unimplemented","answer":null,"error":"Unimplemented","duration_ns":null}"#
+    );
+}
+
+#[test]
+fn test_standalone_duration() {
+    let mut record = Record::ok(4, 1, "13".to_string(), Some(Duration::from_micros(400)));
+    assert_eq!(record.standalone_duration(), Some(Duration::from_micros(400)));
+
+    // charged half of a 600us parse, it would have taken 700us on its own
+    record.parse_duration = Some(Duration::from_micros(300));
+    record.shared_parse = Some(Duration::from_micros(600));
+    assert_eq!(record.standalone_duration(), Some(Duration::from_micros(700)));
+    assert!(record.to_json().contains(r#""parse_ns":300000,"parse_amortized":true"#));
+}
@@ -0,0 +1,141 @@
+use std::{io::{stdout, Write}, sync::{atomic::{AtomicBool, Ordering}, Arc, Mutex}, thread::{self, JoinHandle}, time::{Duration, Instant}};
+
+use crate::solutions;
+
+/*
+Live progress for text output on a terminal.
+
+Results are printed as rows of a table as they come in, and the last line is redrawn every tick
+with a spinner and how long the running part has been going, so a slow part is obvious.
+The runner says what it's working on with `running`, from whichever thread it's on.
+
+Colours can be turned off with the NO_COLOR environment variable.
+*/
+
+const TICK: Duration = Duration::from_millis(80);
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+// also held while drawing, so rows and the spinner line don't get interleaved
+static RUNNING: Mutex<Option<Running>> = Mutex::new(None);
+
+struct Running {
+    year: u16,
+    day: u8,
+    // `None` while parsing input shared between parts
+    part: Option<u8>,
+    since: Instant,
+}
+
+pub fn running(year: u16, day: u8, part: Option<u8>) {
+    if let Ok(mut running) = RUNNING.lock() {
+        *running = Some(Running { year, day, part, since: Instant::now() });
+    }
+}
+
+pub struct Live {
+    stop: Arc<AtomicBool>,
+    ticker: Option<JoinHandle<()>>,
+}
+
+impl Live {
+    pub fn start(header: &str) -> Self {
+        println!("{header}");
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let ticker_stop = Arc::clone(&stop);
+
+        let ticker = thread::spawn(move || {
+            let mut frame = 0;
+
+            while !ticker_stop.load(Ordering::Relaxed) {
+                thread::sleep(TICK);
+                frame = (frame + 1) % SPINNER.len();
+
+                let Ok(running) = RUNNING.lock() else {
+                    break;
+                };
+
+                if let Some(running) = running.as_ref() {
+                    let title = solutions::get(running.year, running.day)
+                        .map(|solution| solution.title)
+                        .unwrap_or_default();
+                    let part = match running.part {
+                        Some(1) => "part one",
+                        Some(_) => "part two",
+                        None => "parsing",
+                    };
+
+                    print!(
+                        "{CLEAR_LINE}{} Day {}: {title}, {part} {:.1}s",
+                        SPINNER[frame],
+                        running.day,
+                        running.since.elapsed().as_secs_f32(),
+                    );
+                    let _ = stdout().flush();
+                }
+            }
+        });
+
+        Self {
+            stop,
+            ticker: Some(ticker),
+        }
+    }
+
+    // prints a finished row above the spinner
+    pub fn row(&self, row: &str) {
+        if let Ok(mut running) = RUNNING.lock() {
+            *running = None;
+            println!("{CLEAR_LINE}{row}");
+        }
+    }
+}
+
+impl Drop for Live {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(ticker) = self.ticker.take() {
+            let _ = ticker.join();
+        }
+
+        if let Ok(mut running) = RUNNING.lock() {
+            *running = None;
+        }
+        print!("{CLEAR_LINE}");
+        let _ = stdout().flush();
+    }
+}
+
+pub fn colour_enabled() -> bool {
+    std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty())
+}
+
+pub fn paint(text: &str, colour: Colour, enabled: bool) -> String {
+    if !enabled {
+        return text.to_string();
+    }
+
+    let code = match colour {
+        Colour::Green => "32",
+        Colour::Red => "31",
+        Colour::Yellow => "33",
+        Colour::Dim => "2",
+    };
+
+    format!("\x1b[{code}m{text}\x1b[0m")
+}
+
+#[derive(Clone, Copy)]
+pub enum Colour {
+    Green,
+    Red,
+    Yellow,
+    Dim,
+}
+
+#[test]
+fn test_paint() {
+    assert_eq!(paint("ok", Colour::Green, true), "\x1b[32mok\x1b[0m");
+    assert_eq!(paint("ok", Colour::Green, false), "ok");
+}
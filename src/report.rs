@@ -0,0 +1,164 @@
+use std::{fs::{read_to_string, write}, path::Path};
+
+use crate::{allocs::format_bytes, answers::Verdict, cli::ReportOptions, history::git_commit, output::{Record, Status}, solutions};
+
+/*
+`report` subcommand, a summary of every registered solution:
+a Markdown table on stdout (or --output), optionally a self-contained HTML page (--html),
+and optionally the same table in README.md between the report markers (--readme).
+
+--redact hides answers, for sharing the report without giving answers away.
+*/
+
+const README_START: &str = "<!-- report start -->";
+const README_END: &str = "<!-- report end -->";
+
+const HEADER: [&str; 6] = ["Day", "Part", "Status", "Answer", "Runtime", "Allocations"];
+const ALIGN: [&str; 6] = ["---", "---:", "---", "---", "---:", "---:"];
+
+pub fn write_report(records: &[Record], year: u16, options: &ReportOptions) -> Result<(), String> {
+    let rows: Vec<[String; 6]> = records.iter()
+        .map(|record| row(record, year, options.redact))
+        .collect();
+
+    let markdown = markdown(&rows);
+
+    match &options.output {
+        Some(path) => {
+            write(path, &markdown).map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+            println!("Wrote {}", path.display());
+        },
+        None => print!("{markdown}"),
+    }
+
+    if let Some(path) = &options.html {
+        write(path, html(&rows, year)).map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+
+    if options.readme {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("README.md");
+        let readme = read_to_string(&path)
+            .map_err(|e| format!("Couldn't read {}: {e}", path.display()))?;
+
+        write(&path, replace_section(&readme, &markdown)?)
+            .map_err(|e| format!("Couldn't write {}: {e}", path.display()))?;
+        println!("Updated {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn row(record: &Record, year: u16, redact: bool) -> [String; 6] {
+    let day = match solutions::get(year, record.day) {
+        Some(solution) => format!("{}: {}", record.day, solution.title),
+        None => record.day.to_string(),
+    };
+
+    let status = match (&record.verdict, record.status) {
+        (Some(Verdict::Correct), _) => "✓ correct".to_string(),
+        (Some(Verdict::Wrong { .. }), _) => "✗ wrong".to_string(),
+        (_, status) => status.name().to_string(),
+    };
+
+    let answer = match &record.answer {
+        Some(_) if redact => "redacted".to_string(),
+        Some(answer) => answer.clone(),
+        None => String::new(),
+    };
+
+    let runtime = match (record.status, record.duration) {
+        (Status::Ok, Some(duration)) => format!("{:.3}ms", duration.as_micros() as f32 / 1000.0),
+        _ => String::new(),
+    };
+
+    let allocs = record.allocs
+        .map(|allocs| format!("{} ({}, peak {})", allocs.count, format_bytes(allocs.bytes), format_bytes(allocs.peak)))
+        .unwrap_or_default();
+
+    [day, record.part.to_string(), status, answer, runtime, allocs]
+}
+
+fn markdown(rows: &[[String; 6]]) -> String {
+    let line = |cells: &[String]| -> String {
+        let cells: Vec<String> = cells.iter()
+            .map(|cell| cell.replace('|', "\\|"))
+            .collect();
+        format!("| {} |\n", cells.join(" | "))
+    };
+
+    let mut markdown = line(&HEADER.map(String::from));
+    markdown += &line(&ALIGN.map(String::from));
+
+    for row in rows {
+        markdown += &line(row);
+    }
+
+    markdown += &format!("\nGenerated at commit `{}`\n", git_commit());
+    markdown
+}
+
+fn html(rows: &[[String; 6]], year: u16) -> String {
+    let escape = |s: &str| -> String {
+        s.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    };
+
+    let cells = |tag: &str, cells: &[String]| -> String {
+        cells.iter()
+            .map(|cell| format!("<{tag}>{}</{tag}>", escape(cell)))
+            .collect()
+    };
+
+    let mut table = format!("<tr>{}</tr>\n", cells("th", &HEADER.map(String::from)));
+    for row in rows {
+        table += &format!("<tr>{}</tr>\n", cells("td", row));
+    }
+
+    format!("\
+<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>Advent of Code {year}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; }}
+th, td {{ border: 1px solid #ccc; padding: 0.3em 0.8em; text-align: left; }}
+td:nth-child(2), td:nth-child(5), td:nth-child(6) {{ text-align: right; font-variant-numeric: tabular-nums; }}
+tr:nth-child(even) {{ background: #f6f6f6; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code {year}</h1>
+<table>
+{table}</table>
+<p>Generated at commit <code>{}</code></p>
+</body>
+</html>
+", escape(git_commit()))
+}
+
+// swaps out whatever is between the report markers, keeping the markers
+fn replace_section(readme: &str, section: &str) -> Result<String, String> {
+    let start = readme.find(README_START)
+        .ok_or_else(|| format!("Couldn't find `{README_START}` in README.md"))?
+        + README_START.len();
+    let end = start + readme[start..].find(README_END)
+        .ok_or_else(|| format!("Couldn't find `{README_END}` in README.md"))?;
+
+    Ok(format!("{}\n{section}{}", &readme[..start], &readme[end..]))
+}
+
+#[test]
+fn test_replace_section() {
+    let readme = "# Title\n<!-- report start -->\nold\n<!-- report end -->\nrest\n";
+
+    assert_eq!(
+        replace_section(readme, "| new |\n").unwrap(),
+        "# Title\n<!-- report start -->\n| new |\n<!-- report end -->\nrest\n"
+    );
+    assert!(replace_section("# Title\n", "| new |\n").is_err());
+}
@@ -1,97 +1,681 @@
-use std::fs::read_to_string;
-
-use crate::{cli::Config, solutions::{solved::Solved, *}};
-
-pub fn dispatch(day: u8, part: u8) -> &'static dyn Solved {
-    match (day, part) {
-        (1, 1) => &day_1::part_one,
-        (1, 2) => &day_1::part_two,
-        (2, 1) => &day_2::part_one,
-        (2, 2) => &day_2::part_two,
-        (3, 1) => &day_3::part_one,
-        (3, 2) => &day_3::part_two,
-        (4, 1) => &day_4::part_one,
-        (4, 2) => &day_4::part_two,
-        (5, 1) => &day_5::part_one,
-        (5, 2) => &day_5::part_two,
-        (6, 1) => &day_6::part_one,
-        (6, 2) => &day_6::part_two,
-        (7, 1) => &day_7::part_one,
-        (7, 2) => &day_7::part_two,
-        (8, 1) => &day_8::part_one,
-        (8, 2) => &day_8::part_two,
-        (9, 1) => &day_9::part_one,
-        (9, 2) => &day_9::part_two,
-        //(10, 1) => &day_10::part_one,
-        //(10, 2) => &day_10::part_two,
-        //(11, 1) => &day_11::part_one,
-        //(11, 2) => &day_11::part_two,
-        //(12, 1) => &day_12::part_one,
-        //(12, 2) => &day_12::part_two,
-        //(13, 1) => &day_13::part_one,
-        //(13, 2) => &day_13::part_two,
-        //(13, 1) => &day_13::part_one,
-        //(13, 2) => &day_13::part_two,
-        //(14, 1) => &day_14::part_one,
-        //(14, 2) => &day_14::part_two,
-        //(15, 1) => &day_15::part_one,
-        //(15, 2) => &day_15::part_two,
-        //(16, 1) => &day_16::part_one,
-        //(16, 2) => &day_16::part_two,
-        //(17, 1) => &day_17::part_one,
-        //(17, 2) => &day_17::part_two,
-        //(18, 1) => &day_18::part_one,
-        //(18, 2) => &day_18::part_two,
-        //(19, 1) => &day_19::part_one,
-        //(19, 2) => &day_19::part_two,
-        //(20, 1) => &day_20::part_one,
-        //(20, 2) => &day_20::part_two,
-        //(21, 1) => &day_21::part_one,
-        //(21, 2) => &day_21::part_two,
-        //(22, 1) => &day_22::part_one,
-        //(22, 2) => &day_22::part_two,
-        //(23, 1) => &day_23::part_one,
-        //(23, 2) => &day_23::part_two,
-        //(24, 1) => &day_24::part_one,
-        //(24, 2) => &day_24::part_two,
-        //(25, 1) => &day_25::part_one,
-        //(25, 2) => &day_25::part_two,
-        _ => &unimplemented
-    }
-}
-
-pub fn unimplemented(_input: &str) -> Result<String, String> {
-    Err("Unimplemented".to_string())
-}
-
-pub fn run(config: Config) {
-    match config.runmode {
-        crate::cli::Runmode::One { day } => {
-            run_day(day);
-        },
-        crate::cli::Runmode::All => {
-            for day in 1..=25 {
-                run_day(day);
+use std::{collections::BTreeMap, fs::read_dir, io, path::PathBuf, process::ExitCode, sync::{atomic::{AtomicUsize, Ordering}, mpsc, Arc}, thread, time::{Duration, Instant}};
+
+use crate::{allocs::{self, Allocs}, answers, baseline::{self, Baseline}, bench, cli::{Config, InputSource, Runmode}, client::Client, fetch, history::{self, Entry}, input, output::{Output, Record, Status}, progress, report, scaffold, submit::{self, Outcome}, watch, solutions::{self, error::Error, solved::{Day, Parsed, Solved}}, worker::{run_with_timeout, WorkerError}};
+
+pub fn dispatch(year: u16, day: u8, part: u8) -> &'static (dyn Solved + Sync) {
+    solutions::get(year, day)
+        .and_then(|solution| solution.part(part))
+        .unwrap_or(&unimplemented)
+}
+
+pub fn unimplemented(_input: &str) -> Result<String, Error> {
+    Err(Error::Unimplemented)
+}
+
+pub fn run(config: Config) -> ExitCode {
+    let mut output = Output::new(&config);
+
+    match &config.runmode {
+        Runmode::Solve { targets } => {
+            let compare = match &config.compare {
+                Some(path) => match baseline::load(path) {
+                    Ok(baseline) => Some(baseline),
+                    Err(error) => {
+                        eprintln!("Couldn't load baseline: {error}");
+                        return ExitCode::FAILURE;
+                    },
+                },
+                None => None,
+            };
+            let mut current = Baseline::default();
+
+            let mut days: Vec<(u8, Vec<u8>)> = Vec::new();
+            let mut targets = targets.iter().peekable();
+
+            while let Some(first) = targets.next() {
+                let mut parts = vec![first.part];
+                while let Some(next) = targets.next_if(|next| next.day == first.day) {
+                    parts.push(next.part);
+                }
+                days.push((first.day, parts));
+            }
+
+            if config.jobs > 1 {
+                run_parallel(&days, &config, |day, records| {
+                    output.start_day(day);
+                    finish_day(day, records, &config, compare.as_ref(), &mut current, &mut output);
+                    output.end_day();
+                });
+            } else {
+                for (day, parts) in &days {
+                    output.start_day(*day);
+                    let records = solve_day(*day, parts, &config);
+                    finish_day(*day, records, &config, compare.as_ref(), &mut current, &mut output);
+                    output.end_day();
+                }
+            }
+
+            if let Some(path) = &config.save_baseline
+                && let Err(error) = baseline::save(path, &current)
+            {
+                eprintln!("Couldn't save baseline: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Runmode::History { day } => {
+            history::print_history(config.year, *day);
+        },
+        Runmode::Watch { day } => {
+            if let Err(error) = watch::watch(config.year, *day, &config.input_dir) {
+                eprintln!("Stopped watching day {day}: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Runmode::Report(options) => {
+            let mut current = Baseline::default();
+
+            for &(day, _) in solutions::days(config.year) {
+                let records = solve_day(day, &[1, 2], &config);
+                finish_day(day, records, &config, None, &mut current, &mut output);
+            }
+
+            if let Err(error) = report::write_report(output.records(), config.year, options) {
+                eprintln!("Couldn't write report: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Runmode::Fetch { days, remote } => {
+            if let Err(error) = fetch::fetch_days(remote, config.year, days, &config.input_dir) {
+                eprintln!("Couldn't fetch inputs: {error}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Runmode::Submit { day, part, remote } => {
+            let answer = match solve_day(*day, &[*part], &config).pop() {
+                Some(Record { status: Status::Ok, answer: Some(answer), .. }) => answer,
+                record => {
+                    let error = record.and_then(|record| record.error).unwrap_or_default();
+                    eprintln!("Couldn't solve day {day} part {part}, so there's nothing to submit: {error}");
+                    return ExitCode::FAILURE;
+                },
+            };
+
+            let submitted = Client::new(remote)
+                .and_then(|mut client| submit::submit_answer(&mut client, config.year, *day, *part, &answer));
+
+            match submitted {
+                Ok(Outcome::Correct) => {},
+                Ok(_) => return ExitCode::FAILURE,
+                Err(error) => {
+                    eprintln!("Couldn't submit {answer}: {error}");
+                    return ExitCode::FAILURE;
+                },
+            }
+        },
+        Runmode::New { day, title } => {
+            if let Err(error) = scaffold::new_day(config.year, *day, title.as_deref(), &config.input_dir) {
+                eprintln!("Couldn't create day {day}: {error}");
+                return ExitCode::FAILURE;
             }
         },
     }
+
+    output.finish()
 }
 
-fn run_day(day: u8) {
-    println!("Day {day}");
+/*
+--jobs runs days on a pool of threads, for quick correctness checks.
+Days still get printed in order, each one as soon as it and every day before it has finished.
+Solutions compete for the CPU, so runtimes are left out rather than shown and recorded.
+*/
+fn run_parallel(days: &[(u8, Vec<u8>)], config: &Config, mut finish: impl FnMut(u8, Vec<Record>)) {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..config.jobs.min(days.len()) {
+            let (next, sender) = (&next, sender.clone());
+
+            scope.spawn(move || {
+                while let Some((day, parts)) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let records = solve_day(*day, parts, config);
+                    if sender.send((*day, records)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = BTreeMap::new();
+        let mut in_order = days.iter().map(|(day, _)| *day).peekable();
+
+        for (day, records) in receiver {
+            finished.insert(day, records);
+
+            while let Some(records) = in_order.peek().and_then(|day| finished.remove(day)) {
+                let day = in_order.next().unwrap_or_default();
+                finish(day, records);
+            }
+        }
+    });
+}
+
+// everything that has to happen in day order, once a day's parts have run
+// `current` collects this run's runtimes, for --save-baseline
+fn finish_day(day: u8, records: Vec<Record>, config: &Config, compare: Option<&Baseline>, current: &mut Baseline, output: &mut Output) {
+    // test and alternate inputs, or someone else's input, don't give comparable runtimes
+    let timed = !config.test && !config.alternates && matches!(config.input, InputSource::Default);
+
+    for mut record in records {
+        let part = record.part;
+
+        if config.jobs > 1 {
+            record.duration = None;
+            record.parse_duration = None;
+            record.shared_parse = None;
+            output.emit(record);
+            continue;
+        }
+
+        if let (true, Status::Ok, Some(duration)) = (timed, record.status, record.standalone_duration()) {
+            let entry = baseline::Entry {
+                duration,
+                std_dev: record.bench.as_ref().map(|stats| stats.std_dev),
+            };
+
+            if let Some(baseline) = compare {
+                record.change = Some(baseline.compare(config.year, day, part, entry));
+            }
+            current.insert(config.year, day, part, entry);
 
-    if let Ok(input) = get_input_from_file(day) {
-        println!("Part One");
-        dispatch(day, 1).print_timed(&input);
-        println!("Part Two");
-        dispatch(day, 2).print_timed(&input);
+            if record.bench.is_none() {
+                let answer = record.answer.as_deref().unwrap_or_default();
+
+                if let Err(error) = history::record(&Entry::new(config.year, day, part, answer, duration)) {
+                    eprintln!("Couldn't save runtime: {error}");
+                }
+            }
+        }
+
+        output.emit(record);
+    }
+}
+
+// runs the parts of a day without printing anything, so days can run on other threads
+fn solve_day(day: u8, parts: &[u8], config: &Config) -> Vec<Record> {
+    let Some(solution) = solutions::get(config.year, day) else {
+        return parts.iter()
+            .map(|&part| solve_error(day, part, Error::Unimplemented))
+            .collect();
+    };
+
+    if config.test {
+        return parts.iter()
+            .map(|&part| test_part(day, part, config))
+            .collect();
+    }
+
+    if config.alternates {
+        return run_alternates(solution, day, parts, config);
+    }
+
+    let input: Result<Arc<str>, _> = get_input(day, config).map(|input| input.into());
+
+    let answers = if config.verify {
+        match answers::load(config.year, day) {
+            Ok(answers) => Some(answers),
+            Err(error) => {
+                eprintln!("Couldn't load answers: {error}");
+                None
+            },
+        }
     } else {
-        println!("Couldn't open file...");
+        None
+    };
+
+    let records = match &input {
+        Ok(input) => run_parts(solution, day, parts, input, config),
+        Err(error) => {
+            parts.iter()
+                .map(|&part| Record::failed(day, part, Status::Err, format!("Couldn't open input file: {error}")))
+                .collect()
+        },
+    };
+
+    records.into_iter()
+        .map(|mut record| {
+            if let Some(answers) = &answers {
+                record.verdict = Some(answers.verify(record.part, record.answer.as_deref()));
+            }
+            record
+        })
+        .collect()
+}
+
+// runs every inputs/day_N/*.txt, to catch assumptions that only hold for one person's input
+fn run_alternates(solution: &'static Day, day: u8, parts: &[u8], config: &Config) -> Vec<Record> {
+    let dir = config.input_dir.join(format!("day_{day}"));
+
+    let mut paths: Vec<PathBuf> = match read_dir(&dir) {
+        Ok(entries) => {
+            entries.filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect()
+        },
+        Err(error) => {
+            let (status, error) = match error.kind() {
+                io::ErrorKind::NotFound => (Status::Skipped, format!("no alternate inputs in {}", dir.display())),
+                _ => (Status::Err, format!("Couldn't read {}: {error}", dir.display())),
+            };
+
+            return parts.iter()
+                .map(|&part| Record::failed(day, part, status, error.clone()))
+                .collect();
+        },
+    };
+
+    paths.sort();
+
+    let mut all_records = Vec::new();
+
+    for path in paths {
+        let name = path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        let input: Result<Arc<str>, _> = input::load(&path).map(|input| input.into());
+
+        let answers = answers::load_alternate(config.year, day, &name)
+            .unwrap_or_else(|error| {
+                eprintln!("Couldn't load answers: {error}");
+                Default::default()
+            });
+
+        let records = match &input {
+            Ok(input) => run_parts(solution, day, parts, input, config),
+            Err(error) => {
+                parts.iter()
+                    .map(|&part| Record::failed(day, part, Status::Err, format!("Couldn't open input file: {error}")))
+                    .collect()
+            },
+        };
+
+        for mut record in records {
+            record.verdict = Some(answers.verify(record.part, record.answer.as_deref()));
+            record.input = Some(name.clone());
+
+            all_records.push(record);
+        }
     }
+
+    all_records
 }
 
-fn get_input_from_file(day: u8) -> Result<String, std::io::Error> {
-    let path = format!("inputs/day_{day}.txt");
+// days with a parse step get parsed once, with both parts solving from the same parsed input
+fn run_parts(solution: &'static Day, day: u8, parts: &[u8], input: &Arc<str>, config: &Config) -> Vec<Record> {
+    let part = |part: u8| solution.part(part).unwrap_or(&unimplemented);
+
+    // a malformed input fails every implemented part before anything is timed,
+    // while a day fresh from `new`, with an empty input, is still just unimplemented
+    if let Err(error) = solution.shape.check(input) {
+        let limit = config.timeouts.for_day(day);
 
-    read_to_string(path)
-}
\ No newline at end of file
+        return parts.iter()
+            .map(|&number| if implemented(part(number), limit) {
+                solve_error(day, number, error.clone())
+            } else {
+                solve_error(day, number, Error::Unimplemented)
+            })
+            .collect();
+    }
+
+    let run_separately = || -> Vec<Record> {
+        parts.iter()
+            .map(|&number| run_part(part(number), day, number, input, config))
+            .collect()
+    };
+
+    // benchmarks time each part as a whole, and a lone part has nothing to share with
+    if parts.len() < 2 || config.bench {
+        return run_separately();
+    }
+
+    let limit = config.timeouts.for_day(day);
+    let worker_input = Arc::clone(input);
+
+    progress::running(config.year, day, None);
+    let result = run_with_timeout(limit, move || {
+        let t0 = Instant::now();
+        let (parsed, allocs) = allocs::count(|| solution.prepare(&worker_input));
+        Some(parsed?.map(|parsed| (parsed, t0.elapsed(), allocs)))
+    });
+
+    match result {
+        Ok(None) => run_separately(),
+        Ok(Some(Ok((parsed, parse, allocs)))) => {
+            let shared = Shared { parsed, parse, allocs, parts: parts.len() };
+
+            parts.iter()
+                .map(|&number| solve_prepared(part(number), day, number, &shared, config))
+                .collect()
+        },
+        Ok(Some(Err(error))) => {
+            parts.iter()
+                .map(|&part| solve_error(day, part, error.clone()))
+                .collect()
+        },
+        Err(error) => {
+            parts.iter()
+                .map(|&part| worker_error(day, part, error.clone()))
+                .collect()
+        },
+    }
+}
+
+// stubs are unimplemented whatever the input, so they're tried on an empty one,
+// which anything real gets through (or fails on) quickly
+fn implemented(solved: &'static (dyn Solved + Sync), limit: Duration) -> bool {
+    !matches!(run_with_timeout(limit, move || solved.solve("")), Ok(Err(Error::Unimplemented)))
+}
+
+// an input parsed once for several parts, with how long that took and what it allocated
+struct Shared {
+    parsed: Parsed,
+    parse: Duration,
+    allocs: Option<Allocs>,
+    parts: usize,
+}
+
+// each part is charged its share of the parse
+fn solve_prepared(solution: &'static (dyn Solved + Sync), day: u8, part: u8, shared: &Shared, config: &Config) -> Record {
+    let limit = config.timeouts.for_day(day);
+    let parsed = Arc::clone(&shared.parsed);
+    let parse = shared.parse / shared.parts as u32;
+    let parse_allocs = shared.allocs.map(|allocs| allocs.amortized(shared.parts));
+
+    progress::running(config.year, day, Some(part));
+    let result = run_with_timeout(limit, move || {
+        let t0 = Instant::now();
+        let (answer, allocs) = allocs::count(|| solution.solve_parsed(&parsed));
+        Ok((answer?, t0.elapsed(), allocs))
+    });
+
+    match result {
+        Ok(Ok((answer, solve, allocs))) => {
+            let mut record = Record::ok(day, part, answer, Some(parse + solve));
+            record.parse_duration = Some(parse);
+            record.shared_parse = Some(shared.parse);
+            record.allocs = parse_allocs.zip(allocs).map(|(parse, solve)| parse.then(&solve));
+            record
+        },
+        Ok(Err(error)) => solve_error(day, part, error),
+        Err(error) => worker_error(day, part, error),
+    }
+}
+
+fn run_part(solution: &'static (dyn Solved + Sync), day: u8, part: u8, input: &Arc<str>, config: &Config) -> Record {
+    let limit = config.timeouts.for_day(day);
+    let worker_input = Arc::clone(input);
+
+    progress::running(config.year, day, Some(part));
+    let result = run_with_timeout(limit, move || {
+        let (result, allocs) = allocs::count(|| solution.solve_split(&worker_input));
+        result.map(|(answer, timing)| (answer, timing, allocs))
+    });
+
+    match result {
+        Ok(Ok((answer, timing, allocs))) => {
+            if config.bench {
+                return bench_part(solution, day, part, input, config);
+            }
+
+            let mut record = Record::ok(day, part, answer, Some(timing.total()));
+            record.parse_duration = timing.parse;
+            record.allocs = allocs;
+            record
+        },
+        Ok(Err(error)) => solve_error(day, part, error),
+        Err(error) => worker_error(day, part, error),
+    }
+}
+
+// passes if every example for the part gives the expected answer
+fn test_part(day: u8, part: u8, config: &Config) -> Record {
+    let examples: Vec<_> = solutions::get(config.year, day)
+        .map(|solution| solution.examples(part).collect())
+        .unwrap_or_default();
+
+    if examples.is_empty() {
+        return Record::failed(day, part, Status::Skipped, "no example input".to_string());
+    }
+
+    let limit = config.timeouts.for_day(day);
+    let solution = dispatch(config.year, day, part);
+
+    progress::running(config.year, day, Some(part));
+
+    for example in &examples {
+        let (input, answer) = (example.input, example.answer);
+
+        match run_with_timeout(limit, move || solution.test(input, answer)) {
+            Ok(Ok(true)) => {},
+            Ok(Ok(false)) => return Record::failed(day, part, Status::Err, format!("Wrong answer, expected `{answer}`")),
+            Ok(Err(error)) => return solve_error(day, part, error),
+            Err(error) => return worker_error(day, part, error),
+        }
+    }
+
+    let answers: Vec<&str> = examples.iter()
+        .map(|example| example.answer)
+        .collect();
+
+    Record::ok(day, part, answers.join(", "), None)
+}
+
+// each run gets the usual time limit, on one worker so a panic in any sample is caught too
+fn bench_part(solution: &'static (dyn Solved + Sync), day: u8, part: u8, input: &Arc<str>, config: &Config) -> Record {
+    let limit = bench::time_limit(config.timeouts.for_day(day), config.samples);
+    let (input, samples) = (Arc::clone(input), config.samples);
+
+    match run_with_timeout(limit, move || bench::bench(solution, &input, samples)) {
+        Ok(Ok((answer, stats))) => {
+            let mut record = Record::ok(day, part, answer, Some(stats.median));
+            record.bench = Some(stats);
+            record
+        },
+        Ok(Err(error)) => solve_error(day, part, error),
+        Err(error) => worker_error(day, part, error),
+    }
+}
+
+fn solve_error(day: u8, part: u8, error: Error) -> Record {
+    let status = match error {
+        Error::Unimplemented => Status::Unimplemented,
+        _ => Status::Err,
+    };
+
+    let mut record = Record::failed(day, part, status, error.to_string());
+    record.error_kind = Some(error.kind());
+    record.error_position = error.position();
+    record
+}
+
+fn worker_error(day: u8, part: u8, error: WorkerError) -> Record {
+    match error {
+        WorkerError::TimedOut(limit) => {
+            Record::failed(day, part, Status::Timeout, format!("timed out after {}s", limit.as_secs_f32()))
+        },
+        WorkerError::Panicked(message) => Record::failed(day, part, Status::Panic, message),
+    }
+}
+
+fn get_input(day: u8, config: &Config) -> Result<String, std::io::Error> {
+    match &config.input {
+        InputSource::Default => input::load(&config.input_dir.join(format!("day_{day}.txt"))),
+        InputSource::File(path) => input::load(path),
+        InputSource::Stdin => input::load_stdin(),
+    }
+}
+
+#[test]
+fn test_run_parts_shares_parse() {
+    use crate::solutions::solved::{Part, Shape, Solution};
+
+    struct Lines;
+
+    impl Solution for Lines {
+        type Parsed = usize;
+
+        fn parse(input: &str) -> Result<usize, Error> {
+            Ok(input.lines().count())
+        }
+
+        fn part_one(lines: &usize) -> Result<String, Error> {
+            Ok(lines.to_string())
+        }
+
+        fn part_two(lines: &usize) -> Result<String, Error> {
+            Ok((lines * 2).to_string())
+        }
+    }
+
+    struct Other;
+
+    impl Solution for Other {
+        type Parsed = String;
+
+        fn parse(input: &str) -> Result<String, Error> {
+            Ok(input.trim().to_string())
+        }
+
+        fn part_one(input: &String) -> Result<String, Error> {
+            Ok(input.clone())
+        }
+
+        fn part_two(input: &String) -> Result<String, Error> {
+            Ok(input.len().to_string())
+        }
+    }
+
+    fn doubled(input: &str) -> Result<String, Error> {
+        Ok((input.lines().count() * 2).to_string())
+    }
+
+    static SHARED: Day = Day {
+        title: "Shared",
+        part_one: &Part::<Lines, 1>::new(),
+        part_two: &Part::<Lines, 2>::new(),
+        shape: Shape::Any,
+        examples: &[],
+    };
+    static UNSPLIT: Day = Day {
+        title: "Unsplit part two",
+        part_one: &Part::<Lines, 1>::new(),
+        part_two: &doubled,
+        shape: Shape::Any,
+        examples: &[],
+    };
+    static DIFFERENT: Day = Day {
+        title: "Different solutions",
+        part_one: &Part::<Lines, 1>::new(),
+        part_two: &Part::<Other, 2>::new(),
+        shape: Shape::Any,
+        examples: &[],
+    };
+    static UNIMPLEMENTED: Day = Day {
+        title: "Unimplemented part two",
+        part_one: &Part::<Lines, 1>::new(),
+        part_two: &unimplemented,
+        shape: Shape::Any,
+        examples: &[],
+    };
+
+    let config = test_config();
+    let input: Arc<str> = "ab\ncd\n".into();
+    let run = |day: &'static Day| run_parts(day, 1, &[1, 2], &input, &config);
+
+    let records = run(&SHARED);
+    assert_eq!(records.iter().map(|record| record.answer.as_deref()).collect::<Vec<_>>(), [Some("2"), Some("4")]);
+    assert!(records.iter().all(|record| record.shared_parse.is_some()));
+
+    // parts from different solutions don't share a parse, and each gets timed on its own
+    let records = run(&UNSPLIT);
+    assert_eq!(records.iter().map(|record| record.answer.as_deref()).collect::<Vec<_>>(), [Some("2"), Some("4")]);
+    assert!(records.iter().all(|record| record.shared_parse.is_none()));
+
+    let records = run(&DIFFERENT);
+    assert_eq!(records.iter().map(|record| record.answer.as_deref()).collect::<Vec<_>>(), [Some("2"), Some("5")]);
+    assert!(records.iter().all(|record| record.shared_parse.is_none()));
+
+    let records = run(&UNIMPLEMENTED);
+    assert_eq!(records[0].answer.as_deref(), Some("2"));
+    assert_eq!(records[1].status, Status::Unimplemented);
+}
+
+#[test]
+fn test_run_parts_unimplemented_on_empty_input() {
+    use crate::solutions::solved::Shape;
+
+    fn stub(_input: &str) -> Result<String, Error> {
+        Err(Error::Unimplemented)
+    }
+
+    fn lines(input: &str) -> Result<String, Error> {
+        Ok(input.lines().count().to_string())
+    }
+
+    // as `new` leaves it
+    static NEW: Day = Day {
+        title: "New",
+        part_one: &stub,
+        part_two: &stub,
+        shape: Shape::Grid,
+        examples: &[],
+    };
+    static HALF: Day = Day {
+        title: "Half done",
+        part_one: &lines,
+        part_two: &stub,
+        shape: Shape::Grid,
+        examples: &[],
+    };
+
+    let config = test_config();
+    let input: Arc<str> = "".into();
+
+    let records = run_parts(&NEW, 1, &[1, 2], &input, &config);
+    assert!(records.iter().all(|record| record.status == Status::Unimplemented));
+
+    // the implemented part still gets told about the input
+    let records = run_parts(&HALF, 1, &[1, 2], &input, &config);
+    assert_eq!(records[0].status, Status::Err);
+    assert!(records[0].error.as_deref().unwrap_or_default().contains("input is empty"));
+    assert_eq!(records[1].status, Status::Unimplemented);
+}
+
+#[cfg(test)]
+fn test_config() -> Config {
+    use std::collections::BTreeSet;
+
+    use crate::{cli::Timeouts, output::Format};
+
+    Config {
+        runmode: Runmode::Solve { targets: BTreeSet::new() },
+        year: 2023,
+        test: false,
+        bench: false,
+        samples: 1,
+        jobs: 1,
+        timeouts: Timeouts::default(),
+        format: Format::Text,
+        verify: false,
+        input: InputSource::Default,
+        input_dir: PathBuf::new(),
+        alternates: false,
+        save_baseline: None,
+        compare: None,
+        max_regression: 10.0,
+    }
+}
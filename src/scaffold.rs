@@ -0,0 +1,132 @@
+use std::{fs::{create_dir_all, read_to_string, write, File, OpenOptions}, io::{ErrorKind, Write}, path::{Path, PathBuf}};
+
+/*
+`new` subcommand, sets up a day from solutions/template.txt:
+1) writes src/solutions/yYEAR/day_N.rs
+2) adds the day to `register!` in src/solutions/yYEAR/mod.rs,
+   first creating that and adding the year to `years!` in src/solutions/mod.rs for a new year
+3) creates an empty inputs/YEAR/day_N.txt
+
+Sources are found relative to the crate so this works from any directory,
+but nothing that already exists gets overwritten.
+*/
+
+const TEMPLATE: &str = include_str!("solutions/template.txt");
+const EMPTY_YEAR: &str = "register! {\n}\n";
+
+pub fn new_day(year: u16, day: u8, title: Option<&str>, input_dir: &Path) -> Result<(), String> {
+    let solutions_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/solutions");
+    let year_dir = solutions_dir.join(format!("y{year}"));
+    let module_path = year_dir.join(format!("day_{day}.rs"));
+    let registry_path = year_dir.join("mod.rs");
+    let years_path = solutions_dir.join("mod.rs");
+
+    let (registry, years) = match read_to_string(&registry_path) {
+        Ok(registry) => (registry, None),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let years = read_to_string(&years_path)
+                .map_err(|e| format!("Couldn't read {}: {e}", years_path.display()))?;
+            (EMPTY_YEAR.to_string(), Some(register(&years, "years!", year, &format!("y{year}"))?))
+        },
+        Err(e) => return Err(format!("Couldn't read {}: {e}", registry_path.display())),
+    };
+    let registry = register(&registry, "register!", day.into(), &format!("day_{day}"))?;
+
+    // check everything before writing anything, so a refusal leaves no half made day behind
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    let source = TEMPLATE.replacen("title: \"\"", &format!("title: {title:?}", title = title.unwrap_or_default()), 1);
+
+    create_dir_all(&year_dir)
+        .map_err(|e| format!("Couldn't create {}: {e}", year_dir.display()))?;
+
+    let mut module = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&module_path)
+        .map_err(|e| format!("Couldn't create {}: {e}", module_path.display()))?;
+    module.write_all(source.as_bytes())
+        .map_err(|e| format!("Couldn't write {}: {e}", module_path.display()))?;
+    println!("Created {}", module_path.display());
+
+    write(&registry_path, registry)
+        .map_err(|e| format!("Couldn't write {}: {e}", registry_path.display()))?;
+    println!("Registered day {day} in {}", registry_path.display());
+
+    if let Some(years) = years {
+        write(&years_path, years)
+            .map_err(|e| format!("Couldn't write {}: {e}", years_path.display()))?;
+        println!("Registered {year} in {}", years_path.display());
+    }
+
+    create_dir_all(input_dir)
+        .map_err(|e| format!("Couldn't create {}: {e}", input_dir.display()))?;
+
+    let input_path: PathBuf = input_dir.join(format!("day_{day}.txt"));
+    match File::create_new(&input_path) {
+        Ok(_) => println!("Created {}", input_path.display()),
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => println!("Left existing {} alone", input_path.display()),
+        Err(e) => return Err(format!("Couldn't create {}: {e}", input_path.display())),
+    }
+
+    Ok(())
+}
+
+// adds `KEY => MODULE,` to a `register!` or `years!` block, keeping it in order
+fn register(registry: &str, block: &str, key: u16, module: &str) -> Result<String, String> {
+    let open = format!("{block} {{");
+    let start = registry.find(&open)
+        .ok_or_else(|| format!("Couldn't find `{open}`"))?;
+    let end = start + registry[start..].find("\n}")
+        .ok_or_else(|| format!("Couldn't find the end of `{block}`"))?;
+
+    let mut lines: Vec<String> = registry[start..end].lines()
+        .skip(1)
+        .map(|line| line.to_string())
+        .collect();
+
+    let registered = |line: &str| -> Option<u16> {
+        line.trim().split_once("=>")?.0.trim().parse().ok()
+    };
+
+    if lines.iter().any(|line| registered(line) == Some(key)) {
+        return Err(format!("`{module}` is already registered"));
+    }
+
+    let idx = lines.iter()
+        .position(|line| registered(line).is_some_and(|registered| registered > key))
+        .unwrap_or(lines.len());
+    lines.insert(idx, format!("    {key} => {module},"));
+
+    Ok(format!("{}{open}\n{}{}", &registry[..start], lines.join("\n"), &registry[end..]))
+}
+
+#[test]
+fn test_register() {
+    let registry = "\
+pub mod solved;
+
+register! {
+    1 => day_1,
+    3 => day_3,
+}
+
+pub fn get() {}
+";
+
+    let registered = register(registry, "register!", 2, "day_2").unwrap();
+    assert!(registered.contains("register! {\n    1 => day_1,\n    2 => day_2,\n    3 => day_3,\n}\n\npub fn get"));
+
+    let registered = register(registry, "register!", 10, "day_10").unwrap();
+    assert!(registered.contains("    3 => day_3,\n    10 => day_10,\n}"));
+
+    assert!(register(registry, "register!", 3, "day_3").is_err());
+
+    let years = "years! {\n    2023 => y2023,\n}\n";
+    assert_eq!(register(years, "years!", 2022, "y2022").unwrap(), "years! {\n    2022 => y2022,\n    2023 => y2023,\n}\n");
+
+    // a new year's registry starts out empty
+    assert_eq!(register(EMPTY_YEAR, "register!", 1, "day_1").unwrap(), "register! {\n    1 => day_1,\n}\n");
+}
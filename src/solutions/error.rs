@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+/*
+The error type every solution returns.
+Parse errors keep track of where in the input things went wrong, so a bad line can actually be found.
+Lines and columns are 1-based, like an editor would show them.
+*/
+
+const SNIPPET_LEN: usize = 40;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Error {
+    Parse {
+        line: usize,
+        column: usize,
+        snippet: String,
+        message: String,
+    },
+    Unimplemented,
+    // the input broke something the solution relies on, e.g. every line having a digit
+    Assumption(String),
+    Internal(String),
+}
+
+impl Error {
+    // parse error at a byte offset into `input`
+    pub fn parse(input: &str, offset: usize, message: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |idx| offset + idx);
+
+        Error::Parse {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].chars().take(SNIPPET_LEN).collect(),
+            message: message.into(),
+        }
+    }
+
+    // parse error for a whole line, `idx` being the 0-based index from `lines().enumerate()`
+    pub fn parse_line(idx: usize, line: &str, message: impl Into<String>) -> Self {
+        Error::parse(line, 0, message).on_line(idx)
+    }
+
+    // converts a nom error, `input` being whatever was handed to the parser
+    pub fn from_nom(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        match error {
+            nom::Err::Incomplete(_) => Error::parse(input, input.len(), "unexpected end of input"),
+            nom::Err::Error(e) | nom::Err::Failure(e) => {
+                let offset = input.len() - e.input.len();
+                Error::parse(input, offset, format!("expected {}", e.code.description()))
+            },
+        }
+    }
+
+    // for errors from a parser that only saw one line, `idx` being the 0-based index of that line
+    pub fn on_line(self, idx: usize) -> Self {
+        match self {
+            Error::Parse { line, column, snippet, message } => Error::Parse {
+                line: line + idx,
+                column,
+                snippet,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    // for errors from a parser that was handed `inner`, a slice of `outer`
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        let offset = inner.as_ptr() as usize - outer.as_ptr() as usize;
+        let before = &outer[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+
+        match self {
+            Error::Parse { line, column, snippet, message } => Error::Parse {
+                line: line + before.matches('\n').count(),
+                column: if line == 1 { column + before[line_start..].chars().count() } else { column },
+                snippet,
+                message,
+            },
+            other => other,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Error::Parse { .. } => "parse",
+            Error::Unimplemented => "unimplemented",
+            Error::Assumption(_) => "assumption",
+            Error::Internal(_) => "internal",
+        }
+    }
+
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::Parse { line, column, .. } => Some((*line, *column)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parse { line, column, snippet, message } => {
+                writeln!(f, "Parse error at line {line}, column {column}: {message}")?;
+                writeln!(f, "{snippet}")?;
+                write!(f, "{:>column$}", "^")
+            },
+            Error::Unimplemented => write!(f, "Unimplemented"),
+            Error::Assumption(message) => write!(f, "Input assumption doesn't hold: {message}"),
+            Error::Internal(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+#[test]
+fn test_parse_position() {
+    let input = "Game 1: 3 blue\nGame 2: 4 purple\n";
+    let offset = input.find("purple").unwrap();
+
+    let error = Error::parse(input, offset, "unknown colour");
+    assert_eq!(error.position(), Some((2, 11)));
+    assert_eq!(error.to_string(), "\
+Parse error at line 2, column 11: unknown colour
+Game 2: 4 purple
+          ^");
+
+    let inner = &input[input.find('\n').unwrap() + 1..];
+    let error = Error::parse(inner, 10, "unknown colour").within(input, inner);
+    assert_eq!(error.position(), Some((2, 11)));
+
+    let error = Error::parse_line(4, "Card 5", "missing `:`");
+    assert_eq!(error.position(), Some((5, 1)));
+}
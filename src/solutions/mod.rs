@@ -1,10 +1,47 @@
+pub mod error;
 pub mod solved;
-pub mod day_1;
-pub mod day_2;
-pub mod day_3;
-pub mod day_4;
-pub mod day_5;
-pub mod day_6;
-pub mod day_7;
-pub mod day_8;
-pub mod day_9;
\ No newline at end of file
+
+use solved::Day;
+
+// declares each day's module and adds its `DAY` to that year's registry
+macro_rules! register {
+    ($($day:literal => $module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub const DAYS: &[(u8, &crate::solutions::solved::Day)] = &[$(($day, &$module::DAY)),*];
+    };
+}
+
+// declares each year's module (src/solutions/yYYYY/mod.rs, which calls `register!`) and adds its days to the registry
+macro_rules! years {
+    ($($year:literal => $module:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub const YEARS: &[(u16, &[(u8, &Day)])] = &[$(($year, $module::DAYS)),*];
+    };
+}
+
+years! {
+    2023 => y2023,
+}
+
+// the default for --year
+pub fn latest_year() -> u16 {
+    YEARS.iter()
+        .map(|(year, _)| *year)
+        .max()
+        .unwrap_or_default()
+}
+
+pub fn days(year: u16) -> &'static [(u8, &'static Day)] {
+    YEARS.iter()
+        .find(|(registered, _)| *registered == year)
+        .map(|(_, days)| *days)
+        .unwrap_or_default()
+}
+
+pub fn get(year: u16, day: u8) -> Option<&'static Day> {
+    days(year).iter()
+        .find(|(registered, _)| *registered == day)
+        .map(|(_, solution)| *solution)
+}
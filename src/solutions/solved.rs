@@ -1,9 +1,162 @@
-use std::time::{Duration, Instant};
+use crate::solutions::error::Error;
+
+use std::{any::{Any, TypeId}, cell::RefCell, marker::PhantomData, sync::{atomic::{AtomicBool, Ordering}, Arc}, time::{Duration, Instant}};
+
+// everything the runner needs to know about a day, each day module declares one of these as `DAY`
+pub struct Day {
+    pub title: &'static str,
+    pub part_one: &'static (dyn Solved + Sync),
+    pub part_two: &'static (dyn Solved + Sync),
+    // checked before anything gets timed
+    pub shape: Shape,
+    pub examples: &'static [Example],
+}
+
+// what a day's input should look like, so a truncated or wrong input fails up front rather than deep in a parser
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    Any,
+    // every line the same length
+    Grid,
+    // exactly this many lines
+    Lines(usize),
+}
+
+impl Shape {
+    pub fn check(&self, input: &str) -> Result<(), Error> {
+        if input.trim().is_empty() {
+            return Err(Error::parse(input, 0, "input is empty"));
+        }
+
+        match self {
+            Shape::Any => Ok(()),
+            Shape::Grid => {
+                let width = input.lines().next().unwrap_or_default().chars().count();
+
+                input.lines()
+                    .enumerate()
+                    .find(|(_, line)| line.chars().count() != width)
+                    .map_or(Ok(()), |(idx, line)| {
+                        let message = format!("expected a grid, but this line is {} wide rather than {width}", line.chars().count());
+                        Err(Error::parse_line(idx, line, message))
+                    })
+            },
+            Shape::Lines(expected) => {
+                let found = input.lines().count();
+
+                if found == *expected {
+                    Ok(())
+                } else {
+                    Err(Error::parse(input, input.len(), format!("expected {expected} lines, found {found}")))
+                }
+            },
+        }
+    }
+}
+
+pub struct Example {
+    pub part: u8,
+    pub input: &'static str,
+    pub answer: &'static str,
+}
+
+impl Day {
+    pub fn part(&self, part: u8) -> Option<&'static (dyn Solved + Sync)> {
+        match part {
+            1 => Some(self.part_one),
+            2 => Some(self.part_two),
+            _ => None,
+        }
+    }
+
+    // parses once for both parts, for days whose parts are both from the same `Solution`; either part can then solve from the result
+    pub fn prepare(&self, input: &str) -> Option<Result<Parsed, Error>> {
+        let solution = self.part_one.solution()?;
+        if self.part_two.solution() != Some(solution) {
+            return None;
+        }
+
+        self.part_one.parse(input)
+    }
+
+    pub fn examples(&self, part: u8) -> impl Iterator<Item = &Example> {
+        self.examples.iter()
+            .filter(move |example| example.part == part)
+    }
+}
+
+thread_local! {
+    static CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+// lets the runner flag a solve on this thread as cancelled once it times out
+pub fn set_cancel_flag(flag: Arc<AtomicBool>) {
+    CANCEL.with(|cancel| *cancel.borrow_mut() = Some(flag));
+}
+
+// long running solutions can poll this and bail out early once the runner has given up
+pub fn cancelled() -> bool {
+    CANCEL.with(|cancel| {
+        cancel.borrow()
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed))
+    })
+}
+
+// a parsed input with its type erased, so it can be handed around by the runner
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+// how long a part took, split into parsing and solving for solutions that separate the two
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Timing {
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse.unwrap_or_default() + self.solve
+    }
+}
 
 pub trait Solved {
-    fn solve(&self, input: &str) -> Result<String, String>;
+    fn solve(&self, input: &str) -> Result<String, Error>;
+
+    // only solutions split into a parse step and a solve step have these, see `Solution`
+    fn parse(&self, _input: &str) -> Option<Result<Parsed, Error>> {
+        None
+    }
 
-    fn solve_timed(&self, input: &str) -> Result<(String, Duration), String> {
+    fn solve_parsed(&self, _parsed: &Parsed) -> Result<String, Error> {
+        Err(Error::Internal("solution doesn't have a parse step".to_string()))
+    }
+
+    // which `Solution` this is a part of, so parts can only share a parse when they'd parse the same way
+    fn solution(&self) -> Option<TypeId> {
+        None
+    }
+
+    // times the parse and solve steps separately where there are both
+    fn solve_split(&self, input: &str) -> Result<(String, Timing), Error> {
+        let t0 = Instant::now();
+
+        let Some(parsed) = self.parse(input) else {
+            let (output, duration) = self.solve_timed(input)?;
+            return Ok((output, Timing { parse: None, solve: duration }));
+        };
+
+        let parsed = parsed?;
+        let t1 = Instant::now();
+        let output = self.solve_parsed(&parsed)?;
+        let t2 = Instant::now();
+
+        Ok((output, Timing {
+            parse: Some(t1.duration_since(t0)),
+            solve: t2.duration_since(t1),
+        }))
+    }
+
+    fn solve_timed(&self, input: &str) -> Result<(String, Duration), Error> {
         let t0 = Instant::now();
         let output = self.solve(input)?;
         let t1 = Instant::now();
@@ -11,13 +164,15 @@ pub trait Solved {
         Ok((output, t1.duration_since(t0)))
     }
 
-    fn test(&self, input: &str, output: &str) -> Result<bool, String> {
+    fn test(&self, input: &str, output: &str) -> Result<bool, Error> {
         let solve_output = self.solve(input)?;
         Ok(&solve_output == output)
     }
 
-    fn print_timed(&self, input: &str) {
-        match self.solve_timed(input) {
+    fn print_timed(&self, input: &str) -> Result<(String, Duration), Error> {
+        let result = self.solve_timed(input);
+
+        match &result {
             Ok((output, duration)) => {
                 let runtime = duration.as_micros() as f32 / 1000.0;
                 println!("Solved in {:.3}ms, answer:", runtime);
@@ -28,13 +183,81 @@ pub trait Solved {
                 println!("{error}");
             },
         }
+
+        result
     }
 }
 
 impl<F> Solved for F 
-    where F: Fn(&str) -> Result<String, String>
+    where F: Fn(&str) -> Result<String, Error>
 {
-    fn solve(&self, input: &str) -> Result<String, String> {
+    fn solve(&self, input: &str) -> Result<String, Error> {
         self(input)
     }
-}
\ No newline at end of file
+}
+
+/*
+A day whose parts both start from the same parsed input.
+Parsing then gets timed on its own, rather than being counted as part of each part's runtime.
+
+Each part goes in the `Day` as a `Part`, e.g. `part_one: &Part::<Almanac, 1>::new()`.
+*/
+pub trait Solution: 'static {
+    type Parsed: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Error>;
+    fn part_one(parsed: &Self::Parsed) -> Result<String, Error>;
+    fn part_two(parsed: &Self::Parsed) -> Result<String, Error>;
+}
+
+pub struct Part<S, const N: u8>(PhantomData<fn() -> S>);
+
+impl<S: Solution, const N: u8> Part<S, N> {
+    pub const fn new() -> Self {
+        Part(PhantomData)
+    }
+
+    fn solve_part(parsed: &S::Parsed) -> Result<String, Error> {
+        match N {
+            1 => S::part_one(parsed),
+            2 => S::part_two(parsed),
+            _ => Err(Error::Internal(format!("there's no part {N}"))),
+        }
+    }
+}
+
+impl<S: Solution, const N: u8> Default for Part<S, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Solution, const N: u8> Solved for Part<S, N> {
+    fn solve(&self, input: &str) -> Result<String, Error> {
+        Self::solve_part(&S::parse(input)?)
+    }
+
+    fn parse(&self, input: &str) -> Option<Result<Parsed, Error>> {
+        Some(S::parse(input).map(|parsed| Arc::new(parsed) as Parsed))
+    }
+
+    fn solution(&self) -> Option<TypeId> {
+        Some(TypeId::of::<S>())
+    }
+
+    fn solve_parsed(&self, parsed: &Parsed) -> Result<String, Error> {
+        let parsed = parsed.downcast_ref::<S::Parsed>()
+            .ok_or_else(|| Error::Internal("parsed input is for a different solution".to_string()))?;
+
+        Self::solve_part(parsed)
+    }
+}
+
+#[test]
+fn test_shape() {
+    assert!(Shape::Grid.check("..#\n#..\n").is_ok());
+    assert_eq!(Shape::Grid.check("..#\n#..\n#.\n").unwrap_err().position(), Some((3, 1)));
+    assert!(Shape::Lines(2).check("Time: 7\nDistance: 9\n").is_ok());
+    assert!(Shape::Lines(2).check("Time: 7\n").is_err());
+    assert!(Shape::Any.check("\n").is_err());
+}
@@ -1,35 +1,50 @@
+use crate::solutions::{error::Error, solved::{Day, Example, Shape}};
+
 /* 
 There's a subtle issue with part two I ran into; "fiveight" should become 58 but a left-to-right parser will take "five" and won't process the remaining "ight" properly. 
 
 The nicest solution would be to write a function that runs until it finds the first occurence of a match from an array of substrings, then returns the index of the substring it found. That way the string is only traversed once. Then, ideally we could enter the input string and substrings into the function backwards to find the last occurrence.
 */
 
-pub fn part_one(input: &str) -> Result<String, String> {
+pub const DAY: Day = Day {
+    title: "Trebuchet?!",
+    part_one: &part_one,
+    part_two: &part_two,
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE_1, answer: _ANSWER_1 },
+        Example { part: 2, input: _EXAMPLE_2, answer: _ANSWER_2 },
+    ],
+};
+
+pub fn part_one(input: &str) -> Result<String, Error> {
     let sum: u32 = input.lines()
-        .map(|line| {
-            line_to_num(line)
+        .enumerate()
+        .map(|(idx, line)| {
+            line_to_num(line).map_err(|e| e.on_line(idx))
         })
-        .sum::<Result<u32, String>>()?;
+        .sum::<Result<u32, Error>>()?;
 
     Ok(sum.to_string())
 }
 
-fn line_to_num(input: &str) -> Result<u32, String> {
+fn line_to_num(input: &str) -> Result<u32, Error> {
     let mut digits = input.chars()
         .filter_map(|c| c.to_digit(10));
 
-    let first = digits.next().ok_or("Line contained no digits")?;
+    let first = digits.next().ok_or_else(|| Error::parse(input, 0, "line contained no digits"))?;
     let last = digits.rev().next().unwrap_or(first);
 
     return Ok(10 * first + last);
 }
 
-pub fn part_two(input: &str) -> Result<String, String> {
+pub fn part_two(input: &str) -> Result<String, Error> {
     let sum: u32 = input.lines()
-        .map(|line| {
-            parse_line(line)
+        .enumerate()
+        .map(|(idx, line)| {
+            parse_line(line).map_err(|e| e.on_line(idx))
         })
-        .sum();
+        .sum::<Result<u32, Error>>()?;
 
     Ok(sum.to_string())
 }
@@ -79,13 +94,14 @@ fn match_last(input: &str, pats: &[&str]) -> Option<usize> {
     return pat_idx;
 } 
 
-fn parse_line(input: &str) -> u32 {
+fn parse_line(input: &str) -> Result<u32, Error> {
     let pats: &[&str] = &[DIGITS, WORDS].concat();
 
-    let first_idx = match_first(input, pats).expect("should always match");
-    let last_idx = match_last(input, pats).expect("should always match");
+    let no_digits = || Error::parse(input, 0, "line contained no digits or digit words");
+    let first_idx = match_first(input, pats).ok_or_else(no_digits)?;
+    let last_idx = match_last(input, pats).ok_or_else(no_digits)?;
 
-    return ((first_idx % 9 + 1) * 10 + (last_idx % 9 + 1)) as u32;
+    Ok(((first_idx % 9 + 1) * 10 + (last_idx % 9 + 1)) as u32)
 }
 
 const _EXAMPLE_1: &str = "\
@@ -7,7 +7,19 @@ use nom::Parser;
 use nom::{sequence::delimited, IResult};
 use nom::character::complete::usize;
 
-pub fn part_one(input: &str) -> Result<String, String> {
+use crate::solutions::{error::Error, solved::{Day, Example, Shape}};
+
+pub const DAY: Day = Day {
+    title: "Cube Conundrum",
+    part_one: &part_one,
+    part_two: &part_two,
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+    ],
+};
+
+pub fn part_one(input: &str) -> Result<String, Error> {
     process_input(input).map(|answer| answer.to_string())
 }
 
@@ -54,9 +66,9 @@ impl Draw {
     }
 }
 
-fn process_input(input: &str) -> Result<usize, String> {
-    input.lines().try_fold(0, |acc, line| {
-        let game = parse_game(line)?;
+fn process_input(input: &str) -> Result<usize, Error> {
+    input.lines().enumerate().try_fold(0, |acc, (idx, line)| {
+        let game = parse_game(line).map_err(|e| e.on_line(idx))?;
         if is_game_possible(&game, &BAG) {
             Ok(acc + game.id)
         } else {
@@ -65,7 +77,7 @@ fn process_input(input: &str) -> Result<usize, String> {
     })
 }
 
-fn parse_game(input: &str) -> Result<Game, String> {
+fn parse_game(input: &str) -> Result<Game, Error> {
     let (_, (id, draws)) = all_consuming((
         delimited(
             tag("Game "), 
@@ -77,7 +89,7 @@ fn parse_game(input: &str) -> Result<Game, String> {
             parse_draw
         )
     )).parse(input)
-        .map_err(|_| "issue parsing game".to_string())?;
+        .map_err(|e| Error::from_nom(input, e))?;
 
     Ok(Game {
         id,
@@ -144,13 +156,13 @@ fn is_game_possible(game: &Game, bag: &Draw) -> bool {
     return true;
 }
 
-pub fn part_two(input: &str) -> Result<String, String> {
+pub fn part_two(input: &str) -> Result<String, Error> {
     parse_two(input).map(|answer| answer.to_string())
 }
 
-fn parse_two(input: &str) -> Result<usize, String> {
-    input.lines().try_fold(0, |acc, line| {
-        let game = parse_game(line)?;
+fn parse_two(input: &str) -> Result<usize, Error> {
+    input.lines().enumerate().try_fold(0, |acc, (idx, line)| {
+        let game = parse_game(line).map_err(|e| e.on_line(idx))?;
         let power = game.min_draw().power();
 
         Ok(acc + power)
@@ -1,11 +1,24 @@
 use std::collections::BTreeMap;
 
+use crate::solutions::{error::Error, solved::{Day, Example, Shape}};
+
+pub const DAY: Day = Day {
+    title: "Gear Ratios",
+    part_one: &part_one,
+    part_two: &part_two,
+    shape: Shape::Grid,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+        Example { part: 2, input: _EXAMPLE, answer: _ANSWER_2 },
+    ],
+};
+
 /*
 this solution is quite a bit more involved than it needs to be, 
 I just wanted to explore solving the problem without allocating.
 Currently solves in about 2.2ms on my computer.
 */
-pub fn part_one(input: &str) -> Result<String, String> {
+pub fn part_one(input: &str) -> Result<String, Error> {
     let schematic = Schematic::new(input);
     let sum: usize = schematic.into_iter()
         .filter(|part_num| {
@@ -183,7 +196,7 @@ Then, scan the surrounds of each number to find a gear and save the gear-number
 
 */
 
-pub fn part_two(input: &str) -> Result<String, String> {
+pub fn part_two(input: &str) -> Result<String, Error> {
     let schematic = Schematic::new(input);
     let gear_list = GearList::from_schematic(&schematic);
     let sum = gear_list.sum();
@@ -1,11 +1,43 @@
 use std::collections::{BTreeSet, VecDeque};
 
-pub fn part_one(input: &str) -> Result<String, String> {
-    let answer: u32 = input.lines()
-        .try_fold(0, |acc, line| -> Result<u32, String> {
-            let card = Card::try_from(line)?;
-            Ok(acc + card.points())
-        })?;
+use crate::solutions::{error::Error, solved::{Day, Example, Part, Shape, Solution}};
+
+pub const DAY: Day = Day {
+    title: "Scratchcards",
+    part_one: &Part::<Scratchcards, 1>::new(),
+    part_two: &Part::<Scratchcards, 2>::new(),
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+        Example { part: 2, input: _EXAMPLE, answer: _ANSWER_2 },
+    ],
+};
+
+struct Scratchcards;
+
+impl Solution for Scratchcards {
+    type Parsed = Vec<Card>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Error> {
+        input.lines()
+            .enumerate()
+            .map(|(idx, line)| Card::try_from(line).map_err(|e| e.on_line(idx)))
+            .collect()
+    }
+
+    fn part_one(cards: &Self::Parsed) -> Result<String, Error> {
+        part_one(cards)
+    }
+
+    fn part_two(cards: &Self::Parsed) -> Result<String, Error> {
+        part_two(cards)
+    }
+}
+
+fn part_one(cards: &[Card]) -> Result<String, Error> {
+    let answer: u32 = cards.iter()
+        .map(|card| card.points())
+        .sum();
 
     Ok(answer.to_string())
 }
@@ -16,10 +48,10 @@ struct Card {
 }
 
 impl TryFrom<&str> for Card {
-    type Error = String;
+    type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let err = String::from("Trouble parsing str to card");
+        let err = || Error::parse(value, 0, "couldn't parse card");
 
         fn parse(input: &str) -> Option<Card> {
             let (_, lists) = input.split_once(':')?;
@@ -45,7 +77,7 @@ impl TryFrom<&str> for Card {
             )
         }
 
-        parse(value).ok_or(err)    
+        parse(value).ok_or_else(err)
     }
 }
 
@@ -66,25 +98,24 @@ impl Card {
     }
 }
 
-pub fn part_two(input: &str) -> Result<String, String> {
-    let mut cards = VecDeque::new();
+fn part_two(cards: &[Card]) -> Result<String, Error> {
+    let mut copies = VecDeque::new();
 
-    let answer = input.lines()
-        .try_fold(0, |acc, line| -> Result<usize, String> {
-            let card = Card::try_from(line)?;
-            let points = card.matches() as usize;
-            let mult = cards.pop_front().unwrap_or(0) + 1;
+    let answer = cards.iter()
+        .fold(0, |acc, card| {
+            let points = card.matches();
+            let mult = copies.pop_front().unwrap_or(0) + 1;
 
             for i in 0..points {
-                if let Some(n) = cards.get_mut(i) {
+                if let Some(n) = copies.get_mut(i) {
                     *n += mult;
                 } else {
-                    cards.push_back(mult);
+                    copies.push_back(mult);
                 }
             }
             // println!("points: {points}, mult: {mult}");
-            Ok(acc + mult)
-        })?;
+            acc + mult
+        });
 
     Ok(answer.to_string())
 }
@@ -102,10 +133,10 @@ const _ANSWER_2: &str = "30";
 
 #[test]
 fn test_part_one() {
-    assert_eq!(_ANSWER, &part_one(_EXAMPLE).unwrap());
+    assert_eq!(_ANSWER, &DAY.part_one.solve(_EXAMPLE).unwrap());
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(_ANSWER_2, &part_two(_EXAMPLE).unwrap());
+    assert_eq!(_ANSWER_2, &DAY.part_two.solve(_EXAMPLE).unwrap());
 }
\ No newline at end of file
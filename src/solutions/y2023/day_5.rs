@@ -1,13 +1,48 @@
 use std::{ops::Range, str::FromStr};
 
-pub fn part_one(input: &str) -> Result<String, String> {
-    let (seeds, maps) = input.split_once("\n\n")
-        .ok_or("bad input".to_string())?;
+use crate::solutions::{error::Error, solved::{Day, Example, Part, Shape, Solution}};
 
-    let mut seeds = parse_seeds(seeds)?;
-    let maps = parse_maps(maps)?;
+pub const DAY: Day = Day {
+    title: "If You Give A Seed A Fertilizer",
+    part_one: &Part::<Almanac, 1>::new(),
+    part_two: &Part::<Almanac, 2>::new(),
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+    ],
+};
 
-    for map in &maps {
+struct Almanac {
+    seeds: Vec<usize>,
+    maps: Vec<RangeMap>,
+}
+
+impl Solution for Almanac {
+    type Parsed = Self;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (seeds, maps) = input.split_once("\n\n")
+            .ok_or_else(|| Error::parse(input, input.len(), "expected a blank line between the seeds and the maps"))?;
+
+        Ok(Almanac {
+            seeds: parse_seeds(seeds)?,
+            maps: parse_maps(maps).map_err(|e| e.within(input, maps))?,
+        })
+    }
+
+    fn part_one(almanac: &Self) -> Result<String, Error> {
+        part_one(almanac)
+    }
+
+    fn part_two(almanac: &Self) -> Result<String, Error> {
+        part_two(almanac)
+    }
+}
+
+fn part_one(almanac: &Almanac) -> Result<String, Error> {
+    let mut seeds = almanac.seeds.clone();
+
+    for map in &almanac.maps {
         for seed in &mut seeds {
             *seed = map.map(*seed);
         }
@@ -18,7 +53,7 @@ pub fn part_one(input: &str) -> Result<String, String> {
     Ok(seeds[0].to_string())
 }
 
-fn parse_seeds(input: &str) -> Result<Vec<usize>, String> {
+fn parse_seeds(input: &str) -> Result<Vec<usize>, Error> {
     let parse = |input: &str| -> Option<Vec<usize>> {
         let (_, nums) = input.split_once(": ")?;
         nums.split(' ')
@@ -27,16 +62,16 @@ fn parse_seeds(input: &str) -> Result<Vec<usize>, String> {
     };
 
     parse(input).ok_or_else(|| {
-            format!("Couldn't parse Vec<usize> from input `{input}`")
+            Error::parse(input, 0, "couldn't parse seeds")
         })
 }
 
-fn parse_maps(input: &str) -> Result<Vec<RangeMap>, String> {
+fn parse_maps(input: &str) -> Result<Vec<RangeMap>, Error> {
     let maps = input.split("\n\n")
         .map(|lines| {
-            lines.parse()
+            lines.parse().map_err(|e: Error| e.within(input, lines))
         })
-        .collect::<Result<Vec<RangeMap>, String>>()?;
+        .collect::<Result<Vec<RangeMap>, Error>>()?;
 
     Ok(maps)
 }
@@ -59,15 +94,16 @@ impl RangeMap {
 }
 
 impl FromStr for RangeMap {
-    type Err = String;
+    type Err = Error;
     
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut mappings = s.lines()
+            .enumerate()
             .skip(1) // title line
-            .map(|line| {
-                line.parse()
+            .map(|(idx, line)| {
+                line.parse().map_err(|e: Error| e.on_line(idx))
             })
-            .collect::<Result<Vec<Mapping>, String>>()?;
+            .collect::<Result<Vec<Mapping>, Error>>()?;
         mappings.sort();
 
         Ok(RangeMap { mappings })
@@ -114,7 +150,7 @@ impl PartialEq for Mapping {
 }
 
 impl FromStr for Mapping {
-    type Err = String;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parse = |s: &str| -> Option<Self> {
@@ -134,13 +170,13 @@ impl FromStr for Mapping {
         };
 
         parse(s).ok_or_else(|| {
-            format!("Couldn't parse Mapping from input `{s}`")
+            Error::parse(s, 0, "couldn't parse mapping")
         })
     }
 }
 
-pub fn part_two(input: &str) -> Result<String, String> {
-    Err("Unimplemented".to_string())
+fn part_two(_almanac: &Almanac) -> Result<String, Error> {
+    Err(Error::Unimplemented)
 }
 
 impl Mapping {
@@ -159,7 +195,7 @@ impl Mapping {
 
 #[test]
 fn test_part_one() {
-    assert_eq!(_ANSWER, &part_one(_EXAMPLE).unwrap());
+    assert_eq!(_ANSWER, &DAY.part_one.solve(_EXAMPLE).unwrap());
 }
 
 #[test]
@@ -175,7 +211,7 @@ fn test_mapping_from_str() {
 
 #[test]
 fn test_part_two() {
-    // assert_eq!(_ANSWER, &part_two(_EXAMPLE).unwrap());
+    // assert_eq!(_ANSWER, &DAY.part_two.solve(_EXAMPLE).unwrap());
 }
 
 const _EXAMPLE: &str = "\
@@ -1,5 +1,16 @@
-pub fn part_one(input: &str) -> Result<String, String> {
-    let races = parse_input(input).ok_or("trouble parsing input".to_string())?;
+use crate::solutions::{error::Error, solved::{Day, Shape}};
+
+pub const DAY: Day = Day {
+    title: "Wait For It",
+    part_one: &part_one,
+    part_two: &part_two,
+    shape: Shape::Lines(2),
+    examples: &[],
+};
+
+pub fn part_one(input: &str) -> Result<String, Error> {
+    let races = parse_input(input)
+        .ok_or_else(|| Error::parse(input, 0, "expected a line of times and a line of distances"))?;
     let answer = solve(&races);
 
     Ok(answer.to_string())
@@ -58,8 +69,9 @@ fn solve(races: &[Race]) -> u64 {
         .fold(1, |acc, race| acc * race.ways_to_win())
 }
 
-pub fn part_two(input: &str) -> Result<String, String> {
-    let race = parse_input_2(input).ok_or("trouble parsing input".to_string())?;
+pub fn part_two(input: &str) -> Result<String, Error> {
+    let race = parse_input_2(input)
+        .ok_or_else(|| Error::parse(input, 0, "expected a line of times and a line of distances"))?;
     let answer = race.ways_to_win();
 
     Ok(answer.to_string())
@@ -1,15 +1,42 @@
 use std::{collections::BTreeMap, str::FromStr};
 
-pub fn part_one(input: &str) -> Result<String, String> {
-    let hand_bets = parse_input(input)
-        .map_err(|_| "trouble parsing input".to_string())?;
+use crate::solutions::{error::Error, solved::{Day, Example, Part, Shape, Solution}};
 
-    let answer = solve(&hand_bets);
+pub const DAY: Day = Day {
+    title: "Camel Cards",
+    part_one: &Part::<CamelCards, 1>::new(),
+    part_two: &Part::<CamelCards, 2>::new(),
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+    ],
+};
+
+struct CamelCards;
+
+impl Solution for CamelCards {
+    type Parsed = Vec<(Hand, Bet)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Error> {
+        parse_input(input)
+    }
+
+    fn part_one(hand_bets: &Self::Parsed) -> Result<String, Error> {
+        part_one(hand_bets)
+    }
+
+    fn part_two(hand_bets: &Self::Parsed) -> Result<String, Error> {
+        part_two(hand_bets)
+    }
+}
+
+fn part_one(hand_bets: &[(Hand, Bet)]) -> Result<String, Error> {
+    let answer = solve(hand_bets);
 
     Ok(answer.to_string())
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
 struct Card(u8);
 
 impl TryFrom<char> for Card {
@@ -38,7 +65,7 @@ impl Card {
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone)]
 struct Hand {
     cards: [Card; 5],
 }
@@ -113,12 +140,15 @@ enum HandType {
 
 type Bet = u32;
 
-fn parse_input(input: &str) -> Result<Vec<(Hand, Bet)>, ()> {
+fn parse_input(input: &str) -> Result<Vec<(Hand, Bet)>, Error> {
     input.lines()
-        .map(|line| -> Result<(Hand, u32), ()> {
-            let (hand, bet) = line.split_once(' ').ok_or(())?;
-            let hand = hand.parse()?;
-            let bet = bet.parse().map_err(|_| ())?;
+        .enumerate()
+        .map(|(idx, line)| -> Result<(Hand, u32), Error> {
+            let err = |message| Error::parse_line(idx, line, message);
+
+            let (hand, bet) = line.split_once(' ').ok_or_else(|| err("expected a hand and a bet"))?;
+            let hand = hand.parse().map_err(|_| err("couldn't parse hand"))?;
+            let bet = bet.parse().map_err(|_| err("couldn't parse bet"))?;
 
             Ok((hand, bet))
         }).collect()
@@ -139,9 +169,9 @@ fn solve(input: &[(Hand, Bet)]) -> Bet {
         .fold(0, |acc, (idx, bet)| acc + (idx as Bet + 1) * bet)
 }
 
-pub fn part_two(input: &str) -> Result<String, String> {
-    let mut hand_bets = parse_input(input)
-        .map_err(|_| "trouble parsing input".to_string())?;
+fn part_two(hand_bets: &[(Hand, Bet)]) -> Result<String, Error> {
+    // jokers change the hands, and the parsed hands are shared with part one
+    let mut hand_bets = hand_bets.to_vec();
 
     hand_bets.iter_mut()
         .for_each(|(hand, _)| hand.jokerify());
@@ -161,7 +191,7 @@ QQQJA 483";
 const _ANSWER: &str = "6440";
 #[test]
 fn test_part_one() {
-    assert_eq!(_ANSWER, &part_one(_EXAMPLE).unwrap());
+    assert_eq!(_ANSWER, &DAY.part_one.solve(_EXAMPLE).unwrap());
 }
 
 #[test]
@@ -172,5 +202,5 @@ fn test_hand_type() {
 
 #[test]
 fn test_part_two() {
-    // assert_eq!(_ANSWER, &part_two(_EXAMPLE).unwrap());
+    // assert_eq!(_ANSWER, &DAY.part_two.solve(_EXAMPLE).unwrap());
 }
\ No newline at end of file
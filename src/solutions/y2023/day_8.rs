@@ -1,22 +1,58 @@
 use std::collections::HashMap;
 
+use crate::solutions::{error::Error, solved::{cancelled, Day, Example, Part, Shape, Solution}};
+
 use nom::{branch::alt, bytes::complete::{tag, take_while_m_n}, character::complete::newline, combinator::map, multi::{fold_many0, many1}, sequence::{delimited, separated_pair, terminated}, IResult, Parser};
 
-pub fn part_one(input: &str) -> Result<String, String> {
-    let (_, (instructions, node_tree)) = parse_input(input)
-        .map_err(|e| e.to_string())?;
-    
+pub const DAY: Day = Day {
+    title: "Haunted Wasteland",
+    part_one: &Part::<Network, 1>::new(),
+    part_two: &Part::<Network, 2>::new(),
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+        Example { part: 2, input: _EXAMPLE_2, answer: _ANSWER_2 },
+    ],
+};
+
+struct Network {
+    instructions: Vec<bool>,
+    nodes: HashMap<String, Node>,
+}
+
+impl Solution for Network {
+    type Parsed = Self;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (_, (instructions, nodes)) = parse_input(input)
+            .map_err(|e| Error::from_nom(input, e))?;
+
+        Ok(Network { instructions, nodes })
+    }
+
+    fn part_one(network: &Self) -> Result<String, Error> {
+        part_one(network)
+    }
+
+    fn part_two(network: &Self) -> Result<String, Error> {
+        part_two(network)
+    }
+}
+
+fn part_one(network: &Network) -> Result<String, Error> {
+    let node_tree = &network.nodes;
+
     let mut current = "AAA";
     let end = "ZZZ";
     let mut counter = 0;
 
-    let mut rl_iter = instructions.into_iter().cycle();
+    let mut rl_iter = network.instructions.iter().copied().cycle();
 
     while current != end {
         counter += 1;
         let next_instr = rl_iter.next().unwrap();
         current = node_tree.get(current)
-            .unwrap()
+            .ok_or_else(|| Error::Assumption(format!("node `{current}` doesn't exist")))?
             .next(next_instr);
     }
 
@@ -39,7 +75,7 @@ fn node_ident(input: &str) -> IResult<&str, &str> {
     take_while_m_n(3, 3, char::is_alphanumeric)(input)
 }
 
-fn parse_node<'a>(input: &'a str) -> IResult<&'a str, Node<'a>> {
+fn parse_node(input: &str) -> IResult<&str, Node> {
     let (rest, (name, (left, right))) = separated_pair(
         node_ident, 
         tag(" = "),
@@ -55,26 +91,26 @@ fn parse_node<'a>(input: &'a str) -> IResult<&'a str, Node<'a>> {
     ).parse(input)?;
 
     let node = Node {
-        name,
-        left,
-        right
+        name: name.to_string(),
+        left: left.to_string(),
+        right: right.to_string(),
     };
 
     Ok((rest, node))
 }
 
-fn parse_nodes<'a>(input: &'a str) -> IResult<&'a str, HashMap<&'a str, Node<'a>>> {
+fn parse_nodes(input: &str) -> IResult<&str, HashMap<String, Node>> {
     fold_many0(
         terminated(parse_node, newline), 
         HashMap::new,
         |mut acc, node| {
-            acc.insert(node.name, node); 
+            acc.insert(node.name.clone(), node); 
             acc
         }     
     ).parse(input)
 }
 
-fn parse_input<'a>(input: &'a str) -> IResult<&'a str, (Vec<bool>, HashMap<&'a str, Node<'a>>)> {
+fn parse_input(input: &str) -> IResult<&str, (Vec<bool>, HashMap<String, Node>)> {
     separated_pair(
         parse_rl,
         newline,
@@ -83,18 +119,18 @@ fn parse_input<'a>(input: &'a str) -> IResult<&'a str, (Vec<bool>, HashMap<&'a s
 }
 
 #[derive(Debug)]
-struct Node<'a> {
-    name: &'a str,
-    left: &'a str,
-    right: &'a str,
+struct Node {
+    name: String,
+    left: String,
+    right: String,
 }
 
-impl<'a> Node<'a> {
-    fn next(&self, right: bool) -> &'a str {
+impl Node {
+    fn next(&self, right: bool) -> &str {
         if right {
-            self.right
+            &self.right
         } else {
-            self.left
+            &self.left
         }
     }
 }
@@ -113,25 +149,27 @@ There are some assumptions that we need to make in order to create an efficient
 Consider the instruction index and node together. If we encounter the same combination twice, we're in a loop. We need to keep track of the overall index (how many times we've moved from node to node) in order to track cycle start time (how long until we enter a cycle) and cycle period. We also need to keep track of all the times we find a Z within a cycle
 */
 
-pub fn part_two(input: &str) -> Result<String, String> {
-    let (_, (instructions, node_tree)) = parse_input(input)
-        .map_err(|e| e.to_string())?;
+fn part_two(network: &Network) -> Result<String, Error> {
+    let node_tree = &network.nodes;
 
-    let mut ghosts = nodes_ending_with(&node_tree, 'A');
+    let mut ghosts = nodes_ending_with(node_tree, 'A');
     //println!("{ghosts:?}");
     let mut counter = 0;
 
-    let mut rl_iter = instructions.into_iter().cycle();
+    let mut rl_iter = network.instructions.iter().copied().cycle();
 
     while !all_z(&ghosts) {
         counter += 1;
+        if counter % (1 << 20) == 0 && cancelled() {
+            return Err(Error::Internal("cancelled".to_string()));
+        }
+
         let next_instr = rl_iter.next().unwrap();
-        ghosts.iter_mut()
-            .for_each(|node_name| {
-                *node_name = node_tree.get(node_name)
-                .unwrap()
+        for node_name in ghosts.iter_mut() {
+            *node_name = node_tree.get(*node_name)
+                .ok_or_else(|| Error::Assumption(format!("node `{node_name}` doesn't exist")))?
                 .next(next_instr);
-            });
+        }
     }
     
     Ok(counter.to_string())
@@ -142,15 +180,15 @@ fn all_z(node_names: &[&str]) -> bool {
         .all(|name| name.ends_with('Z'))
 }
 
-fn nodes_ending_with<'a>(
-    nodes: &HashMap<&'a str, Node<'a>>,
+fn nodes_ending_with(
+    nodes: &HashMap<String, Node>,
     ending: char,
-) -> Vec<&'a str> {
+) -> Vec<&str> {
     nodes
         .values()
         .filter_map(|node| {
             if node.name.ends_with(ending) {
-                Some(node.name)
+                Some(node.name.as_str())
             } else {
                 None
             }
@@ -183,10 +221,10 @@ const _ANSWER_2: &str = "6";
 
 #[test]
 fn test_part_one() {
-    assert_eq!(_ANSWER, &part_one(_EXAMPLE).unwrap());
+    assert_eq!(_ANSWER, &DAY.part_one.solve(_EXAMPLE).unwrap());
 }
 
 #[test]
 fn test_part_two() {
-    assert_eq!(_ANSWER_2, &part_two(_EXAMPLE_2).unwrap());
+    assert_eq!(_ANSWER_2, &DAY.part_two.solve(_EXAMPLE_2).unwrap());
 }
\ No newline at end of file
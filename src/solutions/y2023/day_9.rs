@@ -1,22 +1,46 @@
 use std::ops::Sub;
+
+use crate::solutions::{error::Error, solved::{Day, Example, Shape}};
+
 /* TODO
     clean up the unnecessarily abstract Diff code
     OR
     make all of it abstract and iterator-based (no allocation)
 */
 
-pub fn part_one(input: &str) -> Result<String, String> {
+pub const DAY: Day = Day {
+    title: "Mirage Maintenance",
+    part_one: &part_one,
+    part_two: &part_two,
+    shape: Shape::Any,
+    examples: &[
+        Example { part: 1, input: _EXAMPLE, answer: _ANSWER },
+        Example { part: 2, input: _EXAMPLE, answer: _ANSWER_2 },
+    ],
+};
+
+pub fn part_one(input: &str) -> Result<String, Error> {
     let answer: i32 = input.lines()
-        .map(|line| {
-            let nums: Vec<i32> = line.split(' ')
-                .map(|num| num.parse::<i32>().unwrap())
-                .collect();
-            extrapolate(&nums, nums.len() as i32)
-        }).sum();
+        .enumerate()
+        .map(|(idx, line)| {
+            let nums = parse_line(line).map_err(|e| e.on_line(idx))?;
+            Ok(extrapolate(&nums, nums.len() as i32))
+        }).sum::<Result<i32, Error>>()?;
 
     Ok(answer.to_string())
 }
 
+fn parse_line(line: &str) -> Result<Vec<i32>, Error> {
+    line.split(' ')
+        .map(|num| {
+            num.parse::<i32>().map_err(|_| {
+                let offset = num.as_ptr() as usize - line.as_ptr() as usize;
+                Error::parse(line, offset, format!("`{num}` isn't a number"))
+            })
+        })
+        .collect()
+}
+
 // ideally this function wouldn't have to allocate
 fn find_coeffs(input: &[i32]) -> Vec<i32> {
 
@@ -114,14 +138,13 @@ where
 {}
 
 
-pub fn part_two(input: &str) -> Result<String, String> {
+pub fn part_two(input: &str) -> Result<String, Error> {
     let answer: i32 = input.lines()
-        .map(|line| {
-            let nums: Vec<i32> = line.split(' ')
-                .map(|num| num.parse::<i32>().unwrap())
-                .collect();
-            extrapolate(&nums, -1)
-        }).sum();
+        .enumerate()
+        .map(|(idx, line)| {
+            let nums = parse_line(line).map_err(|e| e.on_line(idx))?;
+            Ok(extrapolate(&nums, -1))
+        }).sum::<Result<i32, Error>>()?;
 
     Ok(answer.to_string())
 }
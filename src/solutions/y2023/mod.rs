@@ -0,0 +1,11 @@
+register! {
+    1 => day_1,
+    2 => day_2,
+    3 => day_3,
+    4 => day_4,
+    5 => day_5,
+    6 => day_6,
+    7 => day_7,
+    8 => day_8,
+    9 => day_9,
+}
@@ -0,0 +1,311 @@
+use std::{fs::{create_dir_all, read_to_string, OpenOptions}, io::{ErrorKind, Write}, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+
+use crate::{answers, client::Client};
+
+/*
+`submit` subcommand, posts a part's answer and records what the site made of it.
+
+Every submission goes into a ledger at answers/YEAR/guesses.tsv, tab separated:
+day, part, unix timestamp, outcome, answer
+
+Before anything gets posted, the ledger is checked so the same wrong answer is never sent twice,
+and numeric answers outside what earlier "too high" and "too low" guesses allow are refused.
+A correct answer is also written to answers/YEAR/day_N.txt, for --verify.
+*/
+
+const LEDGER_FILE: &str = "guesses.tsv";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // with how long the site says to wait, if it said
+    RateLimited(Option<String>),
+}
+
+impl Outcome {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited(_) => "rate_limited",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "correct" => Some(Outcome::Correct),
+            "too_high" => Some(Outcome::TooHigh),
+            "too_low" => Some(Outcome::TooLow),
+            "wrong" => Some(Outcome::Wrong),
+            "rate_limited" => Some(Outcome::RateLimited(None)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Guess {
+    pub day: u8,
+    pub part: u8,
+    pub timestamp: u64,
+    pub outcome: Outcome,
+    pub answer: String,
+}
+
+impl Guess {
+    fn to_line(&self) -> String {
+        format!("{}\t{}\t{}\t{}\t{}", self.day, self.part, self.timestamp, self.outcome.name(), self.answer)
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(5, '\t');
+
+        Some(Self {
+            day: fields.next()?.parse().ok()?,
+            part: fields.next()?.parse().ok()?,
+            timestamp: fields.next()?.parse().ok()?,
+            outcome: Outcome::from_name(fields.next()?)?,
+            answer: fields.next()?.to_string(),
+        })
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct Ledger {
+    guesses: Vec<Guess>,
+}
+
+impl Ledger {
+    // why `answer` shouldn't be submitted, if there's a reason
+    pub fn check(&self, day: u8, part: u8, answer: &str) -> Result<(), String> {
+        let guesses: Vec<&Guess> = self.guesses.iter()
+            .filter(|guess| guess.day == day && guess.part == part)
+            .collect();
+
+        if let Some(guess) = guesses.iter().find(|guess| guess.answer == answer) {
+            match guess.outcome {
+                Outcome::Correct => return Err(format!("{answer} has already been accepted")),
+                Outcome::RateLimited(_) => {},
+                ref outcome => return Err(format!("{answer} was already guessed, and was {}", outcome.name().replace('_', " "))),
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |outcome: Outcome| guesses.iter()
+            .filter(move |guess| guess.outcome == outcome)
+            .filter_map(|guess| guess.answer.parse::<i128>().ok());
+
+        if let Some(high) = bound(Outcome::TooHigh).min()
+            && value >= high
+        {
+            return Err(format!("{answer} can't be right, {high} was already too high"));
+        }
+
+        if let Some(low) = bound(Outcome::TooLow).max()
+            && value <= low
+        {
+            return Err(format!("{answer} can't be right, {low} was already too low"));
+        }
+
+        Ok(())
+    }
+
+    fn parse(input: &str) -> Result<Self, String> {
+        let guesses = input.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Guess::parse(line).ok_or_else(|| format!("Couldn't parse guess from line `{line}`")))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { guesses })
+    }
+}
+
+pub fn ledger_path(year: u16) -> PathBuf {
+    Path::new(answers::ANSWERS_DIR).join(year.to_string()).join(LEDGER_FILE)
+}
+
+pub fn load_ledger(path: &Path) -> Result<Ledger, String> {
+    match read_to_string(path) {
+        Ok(contents) => Ledger::parse(&contents).map_err(|e| format!("{}: {e}", path.display())),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Ledger::default()),
+        Err(e) => Err(format!("{}: {e}", path.display())),
+    }
+}
+
+fn record_guess(path: &Path, guess: &Guess) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        create_dir_all(dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+
+    writeln!(file, "{}", guess.to_line()).map_err(|e| format!("{}: {e}", path.display()))
+}
+
+// checks the ledger at `ledger_path`, posts the answer and adds how it went to the ledger
+pub fn submit(client: &mut Client, ledger_path: &Path, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome, String> {
+    load_ledger(ledger_path)?.check(day, part, answer)?;
+
+    let response = client.post_form(&format!("/{year}/day/{day}/answer"), &[("level", &part.to_string()), ("answer", answer)])?;
+    let outcome = parse_response(&response)?;
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or(0);
+    record_guess(ledger_path, &Guess { day, part, timestamp, outcome: outcome.clone(), answer: answer.to_string() })?;
+
+    Ok(outcome)
+}
+
+// what the runner calls once it has an answer, printing how it went
+pub fn submit_answer(client: &mut Client, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome, String> {
+    let mut answers = answers::load(year, day)?;
+
+    match answers.get(part) {
+        Some(accepted) if accepted == answer => return Err(format!("{answer} has already been accepted")),
+        Some(accepted) => return Err(format!("{accepted} has already been accepted, so {answer} would be wrong")),
+        None => {},
+    }
+
+    let outcome = submit(client, &ledger_path(year), year, day, part, answer)?;
+
+    match &outcome {
+        Outcome::Correct => {
+            answers.set(part, answer)?;
+            answers::save(year, day, &answers)?;
+            println!("{answer} is the right answer, saved to answers/{year}/day_{day}.txt");
+        },
+        Outcome::TooHigh => println!("{answer} isn't the right answer, it's too high"),
+        Outcome::TooLow => println!("{answer} isn't the right answer, it's too low"),
+        Outcome::Wrong => println!("{answer} isn't the right answer"),
+        Outcome::RateLimited(Some(wait)) => println!("{answer} wasn't checked, as the last answer was too recent and {wait}"),
+        Outcome::RateLimited(None) => println!("{answer} wasn't checked, as the last answer was too recent"),
+    }
+
+    Ok(outcome)
+}
+
+// the site answers with a page of HTML, the outcome being in the text of its <article>
+fn parse_response(response: &str) -> Result<Outcome, String> {
+    let text = match (response.find("<article>"), response.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &response[start..end],
+        _ => response,
+    };
+
+    if text.contains("That's the right answer") {
+        Ok(Outcome::Correct)
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text.find("You have ")
+            .and_then(|start| {
+                let wait = &text[start..];
+                wait.find(" left to wait").map(|end| wait[..end].to_lowercase() + " left to wait")
+            });
+        Ok(Outcome::RateLimited(wait))
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Ok(Outcome::TooHigh)
+        } else if text.contains("too low") {
+            Ok(Outcome::TooLow)
+        } else {
+            Ok(Outcome::Wrong)
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Err("That part isn't open, or has already been solved".to_string())
+    } else {
+        Err(format!("Couldn't make sense of the response: {}", strip_tags(text).trim()))
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {},
+        }
+    }
+
+    text
+}
+
+#[test]
+fn test_parse_response() {
+    let page = |text: &str| format!("<html><main>\n<article><p>{text}</p></article>\n</main></html>");
+
+    assert_eq!(parse_response(&page("That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer.")), Ok(Outcome::Correct));
+    assert_eq!(parse_response(&page("That's not the right answer; your answer is too high.  If you're stuck...")), Ok(Outcome::TooHigh));
+    assert_eq!(parse_response(&page("That's not the right answer; your answer is too low.")), Ok(Outcome::TooLow));
+    assert_eq!(parse_response(&page("That's not the right answer.  If you're stuck...")), Ok(Outcome::Wrong));
+    assert_eq!(
+        parse_response(&page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.")),
+        Ok(Outcome::RateLimited(Some("you have 34s left to wait".to_string())))
+    );
+    assert!(parse_response(&page("You don't seem to be solving the right level.  Did you already complete it?")).is_err());
+    assert_eq!(parse_response("<p>Something <b>else</b></p>"), Err("Couldn't make sense of the response: Something else".to_string()));
+}
+
+#[test]
+fn test_ledger() {
+    let ledger = Ledger::parse("\
+5\t2\t1701388800\ttoo_high\t500
+5\t2\t1701388900\ttoo_low\t100
+5\t2\t1701389000\twrong\t250
+5\t2\t1701389100\trate_limited\t300
+5\t1\t1701389200\tcorrect\t42
+").unwrap();
+
+    assert_eq!(Guess::parse(&ledger.guesses[0].to_line()).as_ref(), Some(&ledger.guesses[0]));
+
+    assert!(ledger.check(5, 2, "250").is_err());
+    assert!(ledger.check(5, 2, "500").is_err());
+    assert!(ledger.check(5, 2, "600").is_err());
+    assert!(ledger.check(5, 2, "100").is_err());
+    assert!(ledger.check(5, 2, "300").is_ok());
+    assert!(ledger.check(5, 2, "101").is_ok());
+    assert!(ledger.check(5, 2, "not a number").is_ok());
+    assert!(ledger.check(5, 1, "42").is_err());
+    // other days' guesses don't count
+    assert!(ledger.check(6, 2, "600").is_ok());
+}
+
+#[test]
+fn test_submit() {
+    use std::time::Duration;
+
+    let (base_url, server) = crate::client::serve_once(
+        "<article><p>That's not the right answer; your answer is too high.</p></article>",
+    );
+
+    let dir = std::env::temp_dir().join(format!("aoc-submit-test-{}", std::process::id()));
+    let ledger_path = dir.join(LEDGER_FILE);
+    let mut client = Client::with_session(&base_url, "abc123".to_string())
+        .rate_limit(Duration::ZERO);
+
+    assert_eq!(submit(&mut client, &ledger_path, 2023, 5, 2, "500"), Ok(Outcome::TooHigh));
+
+    let request = server.join().unwrap();
+    assert_eq!(request.line, "POST /2023/day/5/answer HTTP/1.1");
+    assert_eq!(request.body, "level=2&answer=500");
+
+    // refused from the ledger, without asking the server that's no longer there
+    let error = submit(&mut client, &ledger_path, 2023, 5, 2, "700").unwrap_err();
+    assert_eq!(error, "700 can't be right, 500 was already too high");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
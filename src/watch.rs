@@ -0,0 +1,219 @@
+use std::{fs::metadata, path::{Path, PathBuf}, process::Command, thread, time::{Duration, SystemTime}};
+
+/*
+`watch` subcommand, reruns a day whenever its source or input changes.
+
+Changed source has to be rebuilt, so everything runs as a child `cargo` process:
+`cargo test` for the day's tests, then `cargo run -- N --year YEAR --format ndjson` for the answers.
+The few fields watch needs are picked straight out of the ndjson records and compared with the previous run's,
+so a change in answer or runtime stands out.
+
+The files are polled rather than watched, which is plenty for two files.
+*/
+
+const POLL: Duration = Duration::from_millis(500);
+// editors often write a file in more than one go
+const SETTLE: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Debug, PartialEq)]
+struct Summary {
+    part: u8,
+    status: String,
+    // the answer, or the error if it failed
+    answer: String,
+    duration: Option<Duration>,
+}
+
+pub fn watch(year: u16, day: u8, input_dir: &Path) -> Result<(), String> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let paths = [
+        manifest_dir.join(format!("src/solutions/y{year}/day_{day}.rs")),
+        input_dir.join(format!("day_{day}.txt")),
+    ];
+
+    for path in &paths {
+        println!("Watching {}", path.display());
+    }
+
+    let mut last_modified = None;
+    let mut previous: Vec<Summary> = Vec::new();
+
+    loop {
+        if last_modified.as_ref() != Some(&modified(&paths)) {
+            if last_modified.is_some() {
+                thread::sleep(SETTLE);
+            }
+            last_modified = Some(modified(&paths));
+
+            println!();
+            run_tests(year, day, manifest_dir)?;
+
+            let current = run_day(year, day, manifest_dir)?;
+            for summary in &current {
+                let before = previous.iter().find(|before| before.part == summary.part);
+                println!("{}", describe(summary, before));
+            }
+            previous = current;
+
+            println!("Waiting for changes...");
+        }
+
+        thread::sleep(POLL);
+    }
+}
+
+fn modified(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths.iter()
+        .map(|path| metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
+
+fn cargo(subcommand: &str, manifest_dir: &Path) -> Command {
+    let mut command = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    command.arg(subcommand)
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"));
+    command
+}
+
+fn run_tests(year: u16, day: u8, manifest_dir: &Path) -> Result<(), String> {
+    println!("Testing day {day}");
+
+    let mut command = cargo("test", manifest_dir);
+    command.args(["--lib", &format!("solutions::y{year}::day_{day}::")]);
+
+    // test output goes straight through, the result is in there already
+    command.status()
+        .map(|_| ())
+        .map_err(|e| format!("Couldn't run cargo test: {e}"))
+}
+
+fn run_day(year: u16, day: u8, manifest_dir: &Path) -> Result<Vec<Summary>, String> {
+    println!("Running day {day}");
+
+    let mut command = cargo("run", manifest_dir);
+    command.args(["--", &day.to_string(), "--year", &year.to_string(), "--format", "ndjson"]);
+
+    let output = command.output()
+        .map_err(|e| format!("Couldn't run cargo run: {e}"))?;
+
+    // build errors
+    if !output.status.success() && output.stdout.is_empty() {
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| parse_summary(line).ok())
+        .collect())
+}
+
+fn parse_summary(line: &str) -> Result<Summary, String> {
+    Ok(Summary {
+        part: number_field(line, "part").ok_or("missing part")? as u8,
+        status: string_field(line, "status").ok_or("missing status")?,
+        answer: string_field(line, "answer")
+            .or_else(|| string_field(line, "error"))
+            .unwrap_or_default(),
+        duration: number_field(line, "duration_ns").map(Duration::from_nanos),
+    })
+}
+
+// the value after `"key":` in one of our own records, which is safe to search for
+// as any `"` inside a string value is escaped
+fn field<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start = line.find(&format!("\"{key}\":"))? + key.len() + 3;
+    Some(&line[start..])
+}
+
+fn number_field(line: &str, key: &str) -> Option<u64> {
+    let value = field(line, key)?;
+    let len = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    value[..len].parse().ok()
+}
+
+// undoes `output::json_string`, null and anything else that isn't a string is None
+fn string_field(line: &str, key: &str) -> Option<String> {
+    let mut chars = field(line, key)?.strip_prefix('"')?.chars();
+    let mut string = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(string),
+            '\\' => match chars.next()? {
+                'n' => string.push('\n'),
+                'r' => string.push('\r'),
+                't' => string.push('\t'),
+                'u' => {
+                    let hex: String = chars.by_ref().take(4).collect();
+                    string.push(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32)?);
+                },
+                c => string.push(c),
+            },
+            c => string.push(c),
+        }
+    }
+
+    None
+}
+
+fn describe(current: &Summary, previous: Option<&Summary>) -> String {
+    let part = if current.part == 1 { "Part One" } else { "Part Two" };
+    let ms = |duration: Duration| duration.as_micros() as f32 / 1000.0;
+
+    let mut line = match (current.status.as_str(), current.duration) {
+        ("ok", Some(duration)) => format!("{part}: {} in {:.3}ms", current.answer, ms(duration)),
+        (status, _) => format!("{part}: {status}, {}", current.answer.lines().next().unwrap_or_default()),
+    };
+
+    let Some(previous) = previous else {
+        return line;
+    };
+
+    if previous.answer != current.answer || previous.status != current.status {
+        line += &format!(", changed from {}", match previous.status.as_str() {
+            "ok" => previous.answer.clone(),
+            status => status.to_string(),
+        });
+    } else if let (Some(before), Some(now)) = (previous.duration, current.duration) {
+        let change = (now.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+        line += &format!(" (was {:.3}ms, {change:+.1}%)", ms(before));
+    }
+
+    line
+}
+
+#[test]
+fn test_parse_summary() {
+    let line = r#"{"day":8,"part":1,"status":"ok","answer":"20093","error":null,"duration_ns":7624000,"parse_ns":1000,"parse_amortized":true,"allocs":{"count":3,"bytes":10,"peak_bytes":10}}"#;
+    assert_eq!(parse_summary(line), Ok(Summary {
+        part: 1,
+        status: "ok".to_string(),
+        answer: "20093".to_string(),
+        duration: Some(Duration::from_nanos(7624000)),
+    }));
+
+    let line = r#"{"day":8,"part":2,"status":"err","answer":null,"error":"Parse error at line 1\n\"x\"","duration_ns":null}"#;
+    assert_eq!(parse_summary(line).unwrap().answer, "Parse error at line 1\n\"x\"");
+
+    // a key inside a string isn't a key
+    let line = r#"{"day":8,"part":2,"input":"\"status\":\"ok\"","status":"timeout","answer":null,"error":"Timed out","duration_ns":null}"#;
+    assert_eq!(parse_summary(line).unwrap().status, "timeout");
+
+    assert!(parse_summary("Day 8: Haunted Wasteland").is_err());
+}
+
+#[test]
+fn test_describe() {
+    let summary = |answer: &str, micros: u64| Summary {
+        part: 1,
+        status: "ok".to_string(),
+        answer: answer.to_string(),
+        duration: Some(Duration::from_micros(micros)),
+    };
+
+    assert_eq!(describe(&summary("6", 1000), None), "Part One: 6 in 1.000ms");
+    assert_eq!(describe(&summary("6", 1500), Some(&summary("6", 1000))), "Part One: 6 in 1.500ms (was 1.000ms, +50.0%)");
+    assert_eq!(describe(&summary("7", 1500), Some(&summary("6", 1000))), "Part One: 7 in 1.500ms, changed from 6");
+}
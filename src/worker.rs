@@ -0,0 +1,120 @@
+use std::{cell::RefCell, panic::{self, AssertUnwindSafe}, sync::{atomic::{AtomicBool, Ordering}, mpsc::{self, RecvTimeoutError}, Arc, Once}, thread, time::Duration};
+
+use crate::solutions::solved;
+
+/*
+Solutions run on a worker thread so the runner can give up on them, and so a panic only takes down the worker.
+
+Threads can't be killed, so on timeout the worker is flagged as cancelled and detached.
+Long running solutions can poll `solved::cancelled()` to stop early,
+otherwise the worker keeps going in the background until the process exits.
+
+Panics are caught with `catch_unwind`, which only hands back the payload,
+so for worker threads the panic hook stashes the message and location instead of printing them.
+*/
+
+const WORKER_NAME: &str = "solution";
+
+thread_local! {
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+#[derive(Clone)]
+pub enum WorkerError {
+    TimedOut(Duration),
+    Panicked(String),
+}
+
+pub fn run_with_timeout<T, F>(limit: Duration, f: F) -> Result<T, WorkerError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    install_panic_hook();
+
+    let (sender, receiver) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    let worker_cancel = Arc::clone(&cancel);
+
+    let spawned = thread::Builder::new()
+        .name(WORKER_NAME.to_string())
+        .spawn(move || {
+            solved::set_cancel_flag(worker_cancel);
+
+            let result = panic::catch_unwind(AssertUnwindSafe(f))
+                .map_err(|_| {
+                    LAST_PANIC.with(|last| last.borrow_mut().take())
+                        .unwrap_or_else(|| "unknown panic".to_string())
+                });
+
+            // the runner may have stopped listening already
+            let _ = sender.send(result);
+        });
+
+    if let Err(error) = spawned {
+        return Err(WorkerError::Panicked(format!("couldn't start worker: {error}")));
+    }
+
+    match receiver.recv_timeout(limit) {
+        Ok(Ok(output)) => Ok(output),
+        Ok(Err(message)) => Err(WorkerError::Panicked(message)),
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.store(true, Ordering::Relaxed);
+            Err(WorkerError::TimedOut(limit))
+        },
+        Err(RecvTimeoutError::Disconnected) => {
+            Err(WorkerError::Panicked("worker exited without an answer".to_string()))
+        },
+    }
+}
+
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+
+        panic::set_hook(Box::new(move |info| {
+            if thread::current().name() != Some(WORKER_NAME) {
+                default_hook(info);
+                return;
+            }
+
+            let payload = info.payload();
+            let message = payload.downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "panic with non-string payload".to_string());
+
+            let message = match info.location() {
+                Some(location) => format!("{message}, at {location}"),
+                None => message,
+            };
+
+            LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+#[test]
+fn test_panic_is_caught() {
+    let result = run_with_timeout(Duration::from_secs(1), || -> u8 {
+        panic!("oh no");
+    });
+
+    match result {
+        Err(WorkerError::Panicked(message)) => {
+            assert!(message.starts_with("oh no, at src/worker.rs:"), "{message}");
+        },
+        _ => panic!("expected the panic to be caught"),
+    }
+}
+
+#[test]
+fn test_timeout() {
+    let result = run_with_timeout(Duration::from_millis(10), || {
+        thread::sleep(Duration::from_secs(1));
+    });
+
+    assert!(matches!(result, Err(WorkerError::TimedOut(_))));
+}